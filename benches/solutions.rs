@@ -0,0 +1,27 @@
+use aoc2023::{read_data, SOLUTIONS};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Benchmarks the parsing and both parts of every day with an input in the inputs folder.
+///
+/// The parts include the parsing, like when a single part is run.
+fn solutions(c: &mut Criterion) {
+    for solution in SOLUTIONS {
+        let Some(input) = read_data(solution.day)
+            .ok()
+            .filter(|input| !input.is_empty())
+        else {
+            continue;
+        };
+        c.bench_function(&format!("day{} parse", solution.day), |b| {
+            b.iter(|| (solution.parse)(black_box(&input)))
+        });
+        for (part, solve) in [(1, solution.part1), (2, solution.part2)] {
+            c.bench_function(&format!("day{} part {}", solution.day, part), |b| {
+                b.iter(|| solve(black_box(&input)))
+            });
+        }
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
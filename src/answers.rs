@@ -0,0 +1,108 @@
+use std::{collections::HashMap, io::ErrorKind, path::Path};
+
+use anyhow::{bail, Context};
+
+use aoc2023::Answer;
+
+pub const DEFAULT_PATH: &str = "inputs/answers.txt";
+
+/// Known-good answers for the real inputs, keyed by `(day, part)`.
+///
+/// The file has one answer per line as `<day> <part> <answer>`, the answer being the
+/// rest of the line, read as a number when it is one so it compares with the computed
+/// [`Answer`] whatever its type. Empty lines and lines starting with `#` are ignored:
+///
+/// ```text
+/// # day part answer
+/// 1 1 142
+/// 25 2 -
+/// ```
+#[derive(Debug)]
+pub struct Answers(HashMap<(u32, u32), Answer>);
+
+/// How an answer compares to the stored one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+    /// There is no stored answer to compare with.
+    Missing,
+}
+
+impl Answers {
+    /// Reads the answers from `path`, a missing file having no known answers so every
+    /// part is reported as missing.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self(HashMap::new())),
+            Err(e) => return Err(e).with_context(|| format!("Could not read {}", path.display())),
+        };
+        Self::from_str(&content).with_context(|| format!("Invalid answers in {}", path.display()))
+    }
+
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let mut answers = HashMap::new();
+        for (number, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                bail!("Line {}: expected `<day> <part> <answer>`", number + 1);
+            };
+            let (Ok(day), Ok(part)) = (day.parse(), part.parse()) else {
+                bail!("Line {}: invalid day or part", number + 1);
+            };
+            let Ok(answer) = answer.trim().parse();
+            answers.insert((day, part), answer);
+        }
+        Ok(Self(answers))
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &Answer) -> Verdict {
+        match self.0.get(&(day, part)) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::from_str("# day part answer\n1 1 142\n\n25 2 -\n").unwrap();
+        assert_eq!(answers.check(1, 1, &Answer::from(142)), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 1, &Answer::from(281)),
+            Verdict::Fail {
+                expected: Answer::from(142)
+            }
+        );
+        assert_eq!(answers.check(1, 2, &Answer::from(281)), Verdict::Missing);
+        assert_eq!(answers.check(25, 2, &Answer::None), Verdict::Pass);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let answers = Answers::from_file(Path::new("inputs/no_answers.txt")).unwrap();
+        assert_eq!(answers.check(1, 1, &Answer::from(142)), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Answers::from_str("1 1").is_err());
+        assert!(Answers::from_str("one 1 142").is_err());
+    }
+}
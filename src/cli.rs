@@ -0,0 +1,341 @@
+use std::{io::Read, path::PathBuf};
+
+use crate::answers;
+
+use anyhow::{anyhow, bail, Context};
+
+use aoc2023::{read_data, solution, SOLUTIONS};
+
+pub const USAGE: &str = "Usage: aoc2023 [run] <DAYS> [PART] [OPTIONS]
+       aoc2023 verify [DAYS] [--answers <FILE>]
+       aoc2023 tui
+
+Commands:
+  run     Run the solutions and print their answers, the default
+  verify  Compare the answers to the known-good ones, failing on any mismatch
+  tui     Browse the days, run them and scroll through what they visualize
+
+Arguments:
+  <DAYS>  A day (3), a range of days (1..=11 or 1..12) or `all`
+  [PART]  1 or 2, both parts are run when omitted
+
+Options:
+  -i, --input <FILE>    Read the input from FILE instead of inputs/dayN.txt, `-` for stdin
+  -a, --answers <FILE>  Read the answers to verify from FILE instead of inputs/answers.txt
+  --visualize[=<DAYS>]  Show how the solutions work, for all the days run or only DAYS
+  -h, --help            Print this message";
+
+/// Where the puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// `inputs/dayN.txt`.
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    pub fn read(&self, day: u32) -> anyhow::Result<String> {
+        match self {
+            Input::Default => read_data(day),
+            Input::File(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Could not read {}", path.display())),
+            Input::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Could not read the input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Help,
+    Run(Run),
+    Verify(Verify),
+    Tui,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub days: Vec<u32>,
+    pub parts: Vec<u32>,
+    pub input: Input,
+    /// The days to print the visualization of.
+    pub visualize: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verify {
+    pub days: Vec<u32>,
+    pub answers: PathBuf,
+}
+
+/// Parses the command line arguments, without the program name.
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Command> {
+    let mut positional = Vec::new();
+    let mut input = Input::Default;
+    let mut answers = None;
+    let mut visualize = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => {
+                let Some(path) = args.next() else {
+                    bail!("Missing file after {}", arg);
+                };
+                input = if path == "-" {
+                    Input::Stdin
+                } else {
+                    Input::File(PathBuf::from(path))
+                };
+            }
+            "-a" | "--answers" => {
+                let Some(path) = args.next() else {
+                    bail!("Missing file after {}", arg);
+                };
+                answers = Some(PathBuf::from(path));
+            }
+            "--visualize" => visualize = Some(None),
+            _ if arg.starts_with("--visualize=") => {
+                visualize = Some(Some(parse_days(&arg["--visualize=".len()..])?));
+            }
+            "-" => positional.push(arg),
+            _ if arg.starts_with('-') => bail!("Unknown option: {}", arg),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter().peekable();
+    if positional.peek().map(String::as_str) == Some("tui") {
+        positional.next();
+        if let Some(extra) = positional.next() {
+            bail!("Unexpected argument: {}", extra);
+        }
+        if input != Input::Default || answers.is_some() || visualize.is_some() {
+            bail!("tui does not take any option");
+        }
+        return Ok(Command::Tui);
+    }
+    if positional.peek().map(String::as_str) == Some("verify") {
+        positional.next();
+        let days = match positional.next() {
+            None => parse_days("all")?,
+            Some(days) => parse_days(&days)?,
+        };
+        if let Some(extra) = positional.next() {
+            bail!("Unexpected argument: {}", extra);
+        }
+        if input != Input::Default {
+            bail!("verify always uses the inputs in the inputs folder");
+        }
+        if visualize.is_some() {
+            bail!("--visualize cannot be used with verify");
+        }
+        let answers = answers.unwrap_or_else(|| PathBuf::from(answers::DEFAULT_PATH));
+        return Ok(Command::Verify(Verify { days, answers }));
+    }
+    if positional.peek().map(String::as_str) == Some("run") {
+        positional.next();
+    }
+    let Some(days) = positional.next() else {
+        bail!("Missing the days to run");
+    };
+    let days = parse_days(&days)?;
+    let parts = match positional.next() {
+        None => vec![1, 2],
+        Some(part) => vec![parse_part(&part)?],
+    };
+    if let Some(extra) = positional.next() {
+        bail!("Unexpected argument: {}", extra);
+    }
+    if answers.is_some() {
+        bail!("--answers can only be used with verify");
+    }
+    if input != Input::Default && days.len() > 1 {
+        bail!("A custom input can only be used with a single day");
+    }
+    let visualize = match visualize {
+        None => Vec::new(),
+        Some(None) => days.clone(),
+        Some(Some(visualize)) => visualize,
+    };
+    Ok(Command::Run(Run {
+        days,
+        parts,
+        input,
+        visualize,
+    }))
+}
+
+/// Parses `3`, `1..=11`, `1..12` or `all` into the registered days it covers.
+fn parse_days(arg: &str) -> anyhow::Result<Vec<u32>> {
+    if arg == "all" {
+        return Ok(SOLUTIONS.iter().map(|s| s.day).collect());
+    }
+    let days = if let Some((start, end)) = arg.split_once("..=") {
+        parse_day(start)?..=parse_day(end)?
+    } else if let Some((start, end)) = arg.split_once("..") {
+        let end = parse_day(end)?;
+        if end == 0 {
+            bail!("Empty range of days: {}", arg);
+        }
+        parse_day(start)?..=end - 1
+    } else {
+        let day = parse_day(arg)?;
+        day..=day
+    };
+    if days.is_empty() {
+        bail!("Empty range of days: {}", arg);
+    }
+    days.map(|day| {
+        solution(day)
+            .map(|s| s.day)
+            .ok_or_else(|| anyhow!("There is no solution for day {}", day))
+    })
+    .collect()
+}
+
+fn parse_day(arg: &str) -> anyhow::Result<u32> {
+    arg.trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid day: {:?}, expected a number", arg))
+}
+
+fn parse_part(arg: &str) -> anyhow::Result<u32> {
+    match arg {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => bail!("Invalid part: {:?}, expected 1 or 2", arg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> anyhow::Result<Command> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    fn run(days: Vec<u32>, parts: Vec<u32>, input: Input) -> Command {
+        Command::Run(Run {
+            days,
+            parts,
+            input,
+            visualize: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_day_and_part() {
+        assert_eq!(parse("2 1").unwrap(), run(vec![2], vec![1], Input::Default));
+        assert_eq!(
+            parse("run 2").unwrap(),
+            run(vec![2], vec![1, 2], Input::Default)
+        );
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(
+            parse("1..=3").unwrap(),
+            run(vec![1, 2, 3], vec![1, 2], Input::Default)
+        );
+        assert_eq!(
+            parse("1..3 2").unwrap(),
+            run(vec![1, 2], vec![2], Input::Default)
+        );
+        assert_eq!(
+            parse("all").unwrap(),
+            run((1..=25).collect(), vec![1, 2], Input::Default)
+        );
+    }
+
+    #[test]
+    fn test_input() {
+        assert_eq!(
+            parse("3 --input example.txt").unwrap(),
+            run(vec![3], vec![1, 2], Input::File("example.txt".into()))
+        );
+        assert_eq!(
+            parse("-i - 3 2").unwrap(),
+            run(vec![3], vec![2], Input::Stdin)
+        );
+    }
+
+    #[test]
+    fn test_visualize() {
+        let visualize = |args| match parse(args).unwrap() {
+            Command::Run(run) => run.visualize,
+            command => panic!("Expected a run, got {:?}", command),
+        };
+        assert_eq!(visualize("1..=3"), Vec::<u32>::new());
+        assert_eq!(visualize("1..=3 --visualize"), vec![1, 2, 3]);
+        assert_eq!(visualize("--visualize=2..=3 1..=5 2"), vec![2, 3]);
+        assert!(parse("1 --visualize=x").is_err());
+        assert!(parse("verify --visualize").is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("").unwrap_err().to_string(),
+            "Missing the days to run"
+        );
+        assert_eq!(
+            parse("x").unwrap_err().to_string(),
+            "Invalid day: \"x\", expected a number"
+        );
+        assert_eq!(
+            parse("1 3").unwrap_err().to_string(),
+            "Invalid part: \"3\", expected 1 or 2"
+        );
+        assert_eq!(
+            parse("26").unwrap_err().to_string(),
+            "There is no solution for day 26"
+        );
+        assert_eq!(
+            parse("5..=2").unwrap_err().to_string(),
+            "Empty range of days: 5..=2"
+        );
+        assert!(parse("all -i input.txt").is_err());
+        assert!(parse("1 --input").is_err());
+        assert!(parse("1 --verbose").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(
+            parse("verify").unwrap(),
+            Command::Verify(Verify {
+                days: (1..=25).collect(),
+                answers: PathBuf::from(answers::DEFAULT_PATH),
+            })
+        );
+        assert_eq!(
+            parse("verify 1..=2 --answers mine.txt").unwrap(),
+            Command::Verify(Verify {
+                days: vec![1, 2],
+                answers: PathBuf::from("mine.txt"),
+            })
+        );
+        assert!(parse("verify 1 -i input.txt").is_err());
+        assert!(parse("1 --answers mine.txt").is_err());
+    }
+
+    #[test]
+    fn test_tui() {
+        assert_eq!(parse("tui").unwrap(), Command::Tui);
+        assert!(parse("tui 1").is_err());
+        assert!(parse("tui --visualize").is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        assert_eq!(parse("1 --help").unwrap(), Command::Help);
+    }
+}
@@ -1,215 +1,166 @@
 use aoc2023::*;
 
-// use std::io::stdout;
+use answers::{Answers, Verdict};
+use cli::{Command, Run, Verify};
+use report::Timing;
 
-// use crossterm::{
-//     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-//     ExecutableCommand,
-// };
+mod answers;
+mod cli;
+mod report;
+mod tui;
 
-fn main() -> anyhow::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() < 3 {
-        eprintln!("Usage: cargo run -- <day> <part>");
+fn main() {
+    let success = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(run)) => run_days(run),
+        Ok(Command::Verify(verify)) => verify_days(verify),
+        Ok(Command::Tui) => match tui::run() {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                false
+            }
+        },
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            true
+        }
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if !success {
         std::process::exit(1);
     }
+}
 
-    let day: u32 = args[1].parse().expect("Invalid day argument");
-    let part: u32 = args[2].parse().expect("Invalid part argument");
+/// Parses the input of `solution` and runs `parts` on it.
+///
+/// A [`ParseError`] already names the day and where the input is wrong.
+fn run_day(
+    solution: &DaySolution,
+    parts: &[u32],
+    input: &str,
+    visualization: Visualization,
+) -> Result<Vec<Timing>, String> {
+    (solution.run)(input, parts, visualization)
+        .map(|run| Timing::from_run(solution.day, run))
+        .map_err(|e| match e.downcast_ref::<ParseError>() {
+            Some(e) => e.to_string(),
+            None => format!("Day {} failed to parse: {:#}", solution.day, e),
+        })
+}
 
-    if !(1..=25).contains(&day) {
-        eprintln!("Invalid day argument: {}", day);
-        std::process::exit(1);
+/// Runs the days and prints their answers, returns `false` if anything failed.
+fn run_days(run: Run) -> bool {
+    let mut failed = false;
+    let mut timings = Vec::new();
+    let single_day = run.days.len() == 1;
+    for day in run.days {
+        let Some(solution) = solution(day) else {
+            continue;
+        };
+        let input = match run.input.read(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {}: {:#}", day, e);
+                failed = true;
+                continue;
+            }
+        };
+        let visualization = if run.visualize.contains(&day) {
+            Visualization::Print
+        } else {
+            Visualization::Off
+        };
+        let day_timings = match run_day(solution, &run.parts, &input, visualization) {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+                continue;
+            }
+        };
+        if single_day {
+            if let Some(parse) = day_timings.first().and_then(|t| t.parse) {
+                println!("Day {} parsed in {:.2?}", day, parse);
+            }
+        }
+        for timing in day_timings {
+            if timing.answer.is_err() {
+                failed = true;
+                eprintln!("{}", timing.line());
+            } else if single_day {
+                println!("{}", timing.line());
+            }
+            timings.push(timing);
+        }
     }
-
-    if !(1..=2).contains(&part) {
-        eprintln!("Invalid part argument: {}", part);
-        std::process::exit(1);
+    if !single_day && !timings.is_empty() {
+        println!("{}", report::table(&timings));
     }
+    !failed
+}
 
-    // Setup cross-term
-    // stdout().execute(EnterAlternateScreen)?;
-    // enable_raw_mode()?;
-
-    let input = read_data(day);
-
-    if day == 1 {
-        let result = if part == 1 {
-            Day1::solve_part1(&input)
-        } else {
-            Day1::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 2 {
-        let result = if part == 1 {
-            Day2::solve_part1(&input)
-        } else {
-            Day2::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 3 {
-        let result = if part == 1 {
-            Day3::solve_part1(&input)
-        } else {
-            Day3::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 4 {
-        let result = if part == 1 {
-            Day4::solve_part1(&input)
-        } else {
-            Day4::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 5 {
-        let result = if part == 1 {
-            Day5::solve_part1(&input)
-        } else {
-            Day5::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 6 {
-        let result = if part == 1 {
-            Day6::solve_part1(&input)
-        } else {
-            Day6::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 7 {
-        let result = if part == 1 {
-            Day7::solve_part1(&input)
-        } else {
-            Day7::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 8 {
-        let result = if part == 1 {
-            Day8::solve_part1(&input)
-        } else {
-            Day8::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 9 {
-        let result = if part == 1 {
-            Day9::solve_part1(&input)
-        } else {
-            Day9::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 10 {
-        let result = if part == 1 {
-            Day10::solve_part1(&input)
-        } else {
-            Day10::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 11 {
-        let result = if part == 1 {
-            Day11::solve_part1(&input)
-        } else {
-            Day11::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 12 {
-        let result = if part == 1 {
-            Day12::solve_part1(&input)
-        } else {
-            Day12::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 13 {
-        let result = if part == 1 {
-            Day13::solve_part1(&input)
-        } else {
-            Day13::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 14 {
-        let result = if part == 1 {
-            Day14::solve_part1(&input)
-        } else {
-            Day14::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 15 {
-        let result = if part == 1 {
-            Day15::solve_part1(&input)
-        } else {
-            Day15::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 16 {
-        let result = if part == 1 {
-            Day16::solve_part1(&input)
-        } else {
-            Day16::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 17 {
-        let result = if part == 1 {
-            Day17::solve_part1(&input)
-        } else {
-            Day17::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 18 {
-        let result = if part == 1 {
-            Day18::solve_part1(&input)
-        } else {
-            Day18::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 19 {
-        let result = if part == 1 {
-            Day19::solve_part1(&input)
-        } else {
-            Day19::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 20 {
-        let result = if part == 1 {
-            Day20::solve_part1(&input)
-        } else {
-            Day20::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 21 {
-        let result = if part == 1 {
-            Day21::solve_part1(&input)
-        } else {
-            Day21::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 22 {
-        let result = if part == 1 {
-            Day22::solve_part1(&input)
-        } else {
-            Day22::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 23 {
-        let result = if part == 1 {
-            Day23::solve_part1(&input)
-        } else {
-            Day23::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 24 {
-        let result = if part == 1 {
-            Day24::solve_part1(&input)
-        } else {
-            Day24::solve_part2(&input)
-        };
-        println!("{:?}", result);
-    } else if day == 25 {
-        let result = if part == 1 {
-            Day25::solve_part1(&input)
-        } else {
-            Day25::solve_part2(&input)
-        };
-        println!("{:?}", result);
+/// Runs the days on their inputs and compares both parts to the stored answers.
+///
+/// Days without an input or without a stored answer are reported as missing, only
+/// wrong answers and errors make the verification fail.
+fn verify_days(verify: Verify) -> bool {
+    let answers = match Answers::from_file(&verify.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            return false;
+        }
+    };
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in verify.days {
+        let Some(solution) = solution(day) else {
+            continue;
+        };
+        let Ok(input) = read_data(day) else {
+            println!("Day {}: missing input", day);
+            missing += 2;
+            continue;
+        };
+        let day_timings = match run_day(solution, &[1, 2], &input, Visualization::Off) {
+            Ok(timings) => timings,
+            Err(e) => {
+                println!("{}", e);
+                failed += 2;
+                continue;
+            }
+        };
+        for timing in day_timings {
+            let part = timing.part;
+            let answer = match &timing.answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    println!("Day {} part {}: FAIL, {}", day, part, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            match answers.check(day, part, answer) {
+                Verdict::Pass => {
+                    println!("Day {} part {}: pass ({:.2?})", day, part, timing.elapsed);
+                    passed += 1;
+                }
+                Verdict::Fail { expected } => {
+                    println!(
+                        "Day {} part {}: FAIL, expected {} but got {}",
+                        day, part, expected, answer
+                    );
+                    failed += 1;
+                }
+                Verdict::Missing => {
+                    println!("Day {} part {}: missing answer, got {}", day, part, answer);
+                    missing += 1;
+                }
+            }
+        }
     }
-    // stdout().execute(LeaveAlternateScreen)?;
-    // disable_raw_mode()?;
-    Ok(())
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
 }
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use aoc2023::{Answer, DayRun};
+
+/// The outcome of running one part of a day.
+pub struct Timing {
+    pub day: u32,
+    pub part: u32,
+    /// The answer, or the error message if the part failed.
+    pub answer: Result<Answer, String>,
+    /// How long parsing took, only set on the first part of each day.
+    pub parse: Option<Duration>,
+    pub elapsed: Duration,
+}
+
+impl Timing {
+    /// Splits the run of a day in the timing of each part.
+    pub fn from_run(day: u32, run: DayRun) -> Vec<Timing> {
+        run.parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, part)| Timing {
+                day,
+                part: part.part,
+                answer: part.answer.map_err(|e| format!("{:#}", e)),
+                parse: (i == 0).then_some(run.parse),
+                elapsed: part.elapsed,
+            })
+            .collect()
+    }
+
+    /// A single line report, used when only one day runs.
+    pub fn line(&self) -> String {
+        match &self.answer {
+            Ok(answer) => format!(
+                "Day {} part {}: {} ({:.2?})",
+                self.day, self.part, answer, self.elapsed
+            ),
+            Err(e) => format!("Day {} part {} failed: {}", self.day, self.part, e),
+        }
+    }
+}
+
+/// Formats the timings as a table with a total at the bottom.
+pub fn table(timings: &[Timing]) -> String {
+    let answers = timings
+        .iter()
+        .map(|t| match &t.answer {
+            Ok(answer) => answer.to_string(),
+            Err(_) => "failed".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let width = answers
+        .iter()
+        .map(|a| a.chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut table = format!(
+        "Day  Part  {:<width$}  {:>10}  {:>10}\n",
+        "Answer", "Parse", "Solve"
+    );
+    for (timing, answer) in timings.iter().zip(answers) {
+        let parse = timing
+            .parse
+            .map(|parse| format!("{:.2?}", parse))
+            .unwrap_or_default();
+        table += &format!(
+            "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}\n",
+            timing.day,
+            timing.part,
+            answer,
+            parse,
+            format!("{:.2?}", timing.elapsed)
+        );
+    }
+    let parse = timings.iter().filter_map(|t| t.parse).sum::<Duration>();
+    let solve = timings.iter().map(|t| t.elapsed).sum::<Duration>();
+    table += &format!(
+        "{:<9}  {:<width$}  {:>10}  {:>10}",
+        "Total",
+        "",
+        format!("{:.2?}", parse),
+        format!("{:.2?}", solve)
+    );
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let timings = [
+            Timing {
+                day: 1,
+                part: 1,
+                answer: Ok(Answer::from(142)),
+                parse: Some(Duration::from_micros(20)),
+                elapsed: Duration::from_micros(1500),
+            },
+            Timing {
+                day: 25,
+                part: 2,
+                answer: Err("Invalid input".to_string()),
+                parse: None,
+                elapsed: Duration::from_millis(12),
+            },
+        ];
+        let expected = [
+            "Day  Part  Answer       Parse       Solve",
+            "  1     1  142        20.00µs      1.50ms",
+            " 25     2  failed                 12.00ms",
+            "Total                 20.00µs     13.50ms",
+        ];
+        assert_eq!(table(&timings), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_line() {
+        let timing = Timing {
+            day: 3,
+            part: 2,
+            answer: Ok(Answer::from(467835)),
+            parse: None,
+            elapsed: Duration::from_micros(250),
+        };
+        assert_eq!(timing.line(), "Day 3 part 2: 467835 (250.00µs)");
+    }
+}
@@ -1,81 +1,209 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-pub use day1::Day1;
-pub use day10::Day10;
-pub use day11::Day11;
-pub use day12::Day12;
-pub use day13::Day13;
-pub use day14::Day14;
-pub use day15::Day15;
-pub use day16::Day16;
-pub use day17::Day17;
-pub use day18::Day18;
-pub use day19::Day19;
-pub use day2::Day2;
-pub use day20::Day20;
-pub use day21::Day21;
-pub use day22::Day22;
-pub use day23::Day23;
-pub use day24::Day24;
-pub use day25::Day25;
-pub use day3::Day3;
-pub use day4::Day4;
-pub use day5::Day5;
-pub use day6::Day6;
-pub use day7::Day7;
-pub use day8::Day8;
-pub use day9::Day9;
-
-pub fn read_data(day: u32) -> String {
-    let path = format!("inputs/day{}.txt", day);
-    std::fs::read_to_string(path).unwrap()
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+mod animation;
+mod answer;
+mod common;
+mod error;
+mod visualizer;
+
+pub use animation::{Animation, Step};
+pub use answer::Answer;
+pub use error::ParseError;
+pub use visualizer::{Printer, Recorder, Silent, Visualization, Visualizer};
+
+/// Signature of the `solve_part1` and `solve_part2` functions of a [`Solution`], which
+/// never visualize.
+pub type SolveFn = fn(&str) -> anyhow::Result<Answer>;
+
+/// A registered day, everything needed to run it without naming its type.
+pub struct DaySolution {
+    pub day: u32,
+    /// The puzzle title.
+    pub name: &'static str,
+    pub part1: SolveFn,
+    pub part2: SolveFn,
+    /// Only parses the input, to benchmark the parsing on its own.
+    pub parse: fn(&str) -> anyhow::Result<()>,
+    /// Parses the input once and runs the given parts on it, timing each phase.
+    pub run: fn(&str, &[u32], Visualization) -> anyhow::Result<DayRun>,
 }
 
-pub trait Solution {
-    fn solve_part1(input: &str) -> anyhow::Result<String>;
-    fn solve_part2(input: &str) -> anyhow::Result<String>;
+/// The answers of a day along with how long each phase took.
+pub struct DayRun {
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
 }
 
-#[macro_export]
-macro_rules! visualize_print {
-    ($($arg:tt)*) => {
-        #[cfg(feature = "visualize")]
-        {
-            print!($($arg)*);
-        }
-    };
+pub struct PartRun {
+    pub part: u32,
+    pub answer: anyhow::Result<Answer>,
+    pub elapsed: Duration,
+}
+
+fn parse<S: Solution>(input: &str) -> anyhow::Result<()> {
+    parse_located::<S>(input).map(|_| ())
+}
+
+/// Parses the input of `S`, locating its [`ParseError`] in the input so the days don't
+/// have to.
+fn parse_located<S: Solution + ?Sized>(input: &str) -> anyhow::Result<S::Parsed> {
+    S::parse(input).map_err(|e| match e.downcast::<ParseError>() {
+        Ok(error) => error.locate(S::DAY, input).into(),
+        Err(e) => e,
+    })
+}
+
+fn run<S: Solution>(
+    input: &str,
+    parts: &[u32],
+    visualization: Visualization,
+) -> anyhow::Result<DayRun> {
+    match visualization {
+        Visualization::Off => run_with::<S>(input, parts, &Silent),
+        Visualization::Print => run_with::<S>(input, parts, &Printer),
+        Visualization::Record(recorder) => run_with::<S>(input, parts, recorder),
+    }
 }
 
-#[macro_export]
-macro_rules! visualize_println {
-    ($($arg:tt)*) => {
-        #[cfg(feature = "visualize")]
-        {
-            println!($($arg)*);
-        }
+fn run_with<S: Solution>(
+    input: &str,
+    parts: &[u32],
+    vis: &impl Visualizer,
+) -> anyhow::Result<DayRun> {
+    let start = Instant::now();
+    let parsed = parse_located::<S>(input)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&parsed, vis),
+                2 => S::part2(&parsed, vis),
+                _ => Err(anyhow::anyhow!("Invalid part: {}", part)),
+            };
+            PartRun {
+                part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(DayRun { parse, parts })
+}
+
+/// Declares the day modules, re-exports their types and builds [`SOLUTIONS`] from them,
+/// so a new day only needs a line here.
+macro_rules! solutions {
+    ($($module:ident :: $solution:ident, $name:literal;)*) => {
+        $(
+            mod $module;
+            pub use $module::$solution;
+        )*
+
+        /// Every day, in order.
+        pub static SOLUTIONS: &[DaySolution] = &[
+            $(
+                DaySolution {
+                    day: $solution::DAY,
+                    name: $name,
+                    part1: $solution::solve_part1,
+                    part2: $solution::solve_part2,
+                    parse: parse::<$solution>,
+                    run: run::<$solution>,
+                },
+            )*
+        ];
     };
 }
+
+solutions! {
+    day1::Day1, "Trebuchet?!";
+    day2::Day2, "Cube Conundrum";
+    day3::Day3, "Gear Ratios";
+    day4::Day4, "Scratchcards";
+    day5::Day5, "If You Give A Seed A Fertilizer";
+    day6::Day6, "Wait For It";
+    day7::Day7, "Camel Cards";
+    day8::Day8, "Haunted Wasteland";
+    day9::Day9, "Mirage Maintenance";
+    day10::Day10, "Pipe Maze";
+    day11::Day11, "Cosmic Expansion";
+    day12::Day12, "Hot Springs";
+    day13::Day13, "Point of Incidence";
+    day14::Day14, "Parabolic Reflector Dish";
+    day15::Day15, "Lens Library";
+    day16::Day16, "The Floor Will Be Lava";
+    day17::Day17, "Clumsy Crucible";
+    day18::Day18, "Lavaduct Lagoon";
+    day19::Day19, "Aplenty";
+    day20::Day20, "Pulse Propagation";
+    day21::Day21, "Step Counter";
+    day22::Day22, "Sand Slabs";
+    day23::Day23, "A Long Walk";
+    day24::Day24, "Never Tell Me The Odds";
+    day25::Day25, "Snowverload";
+}
+
+/// Finds the registered solution of `day`.
+pub fn solution(day: u32) -> Option<&'static DaySolution> {
+    SOLUTIONS.iter().find(|s| s.day == day)
+}
+
+/// Reads the input of `day` from `inputs/dayN.txt`.
+pub fn read_data(day: u32) -> anyhow::Result<String> {
+    let path = format!("inputs/day{}.txt", day);
+    std::fs::read_to_string(&path).with_context(|| format!("Could not read {}", path))
+}
+
+pub trait Solution {
+    /// The day of the puzzle, reported in parse errors.
+    const DAY: u32;
+
+    /// The input once parsed, shared by both parts.
+    type Parsed;
+
+    /// Reads the input, the [`ParseError`] it returns is located by the caller.
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer>;
+    fn part2(parsed: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer>;
+
+    fn solve_part1(input: &str) -> anyhow::Result<Answer> {
+        Self::part1(&parse_located::<Self>(input)?, &Silent)
+    }
+
+    fn solve_part2(input: &str) -> anyhow::Result<Answer> {
+        Self::part2(&parse_located::<Self>(input)?, &Silent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let days = SOLUTIONS.iter().map(|s| s.day).collect::<Vec<_>>();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+        assert_eq!(solution(25).map(|s| s.name), Some("Snowverload"));
+        assert!(solution(26).is_none());
+    }
+
+    #[test]
+    fn test_answers() {
+        let day1 = solution(1).unwrap();
+        assert_eq!((day1.part1)("1abc2\ntreb7uchet").unwrap(), Answer::UInt(89));
+        assert_eq!((day1.part2)("two1nine").unwrap(), Answer::from(29));
+    }
+
+    #[test]
+    fn test_located() {
+        let error = (solution(7).unwrap().part1)("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Day 7, line 2, column 4: Invalid card: \"X\""
+        );
+    }
+}
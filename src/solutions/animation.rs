@@ -0,0 +1,88 @@
+/// A grid changing step by step, like a walk along a loop, for the TUI to play back.
+///
+/// Only the cells a step changes are kept, so long animations stay small, and a frame is
+/// rebuilt from the starting grid when drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    pub title: String,
+    grid: Vec<Vec<char>>,
+    steps: Vec<Step>,
+}
+
+/// What a frame changes from the previous one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Step {
+    /// The cells drawn again, as `(x, y, c)`, which stay until changed again.
+    pub changes: Vec<(usize, usize, char)>,
+    /// The `(x, y)` cells to highlight in this frame only, like the heads of a walk.
+    pub highlights: Vec<(usize, usize)>,
+    pub caption: String,
+}
+
+impl Animation {
+    /// Starts an animation on the grid drawn by `rows`, a character per cell.
+    pub fn new<S: AsRef<str>>(title: impl Into<String>, rows: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            title: title.into(),
+            grid: rows
+                .into_iter()
+                .map(|row| row.as_ref().chars().collect())
+                .collect(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, step: Step) {
+        self.steps.push(step);
+    }
+
+    /// The number of frames, one per step.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn step(&self, frame: usize) -> &Step {
+        &self.steps[frame]
+    }
+
+    /// The grid once the steps up to `frame` included are applied.
+    pub fn grid_at(&self, frame: usize) -> Vec<Vec<char>> {
+        let mut grid = self.grid.clone();
+        for step in &self.steps[..=frame] {
+            for &(x, y, c) in &step.changes {
+                if let Some(cell) = grid.get_mut(y).and_then(|row| row.get_mut(x)) {
+                    *cell = c;
+                }
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_at() {
+        let mut animation = Animation::new("Walk", ["..", ".."]);
+        animation.push(Step {
+            changes: vec![(0, 0, '#')],
+            highlights: vec![(0, 0)],
+            caption: "Step 1".to_string(),
+        });
+        animation.push(Step {
+            changes: vec![(1, 1, '#'), (5, 5, '#')],
+            highlights: vec![(1, 1)],
+            caption: "Step 2".to_string(),
+        });
+        assert_eq!(animation.len(), 2);
+        assert_eq!(animation.grid_at(0), [['#', '.'], ['.', '.']]);
+        assert_eq!(animation.grid_at(1), [['#', '.'], ['.', '#']]);
+        assert_eq!(animation.step(1).highlights, [(1, 1)]);
+    }
+}
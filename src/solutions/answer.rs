@@ -0,0 +1,117 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The answer to a part of a day.
+///
+/// Numbers compare by value whatever their variant, so `Answer::Int(5)` equals
+/// `Answer::UInt(5)`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Str(String),
+    /// The part has nothing to answer, like the last part of the last day.
+    None,
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::UInt(a), Answer::UInt(b)) => a == b,
+            (Answer::Int(a), Answer::UInt(b)) | (Answer::UInt(b), Answer::Int(a)) => {
+                u64::try_from(*a) == Ok(*b)
+            }
+            (Answer::Str(a), Answer::Str(b)) => a == b,
+            (Answer::None, Answer::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::UInt(v) => write!(f, "{}", v),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+/// Reads an answer back from its [`Display`](fmt::Display) form, numbers first.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(v) = s.parse() {
+            Answer::UInt(v)
+        } else if let Ok(v) = s.parse() {
+            Answer::Int(v)
+        } else if s == "-" {
+            Answer::None
+        } else {
+            Answer::Str(s.to_string())
+        })
+    }
+}
+
+macro_rules! from_number {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+from_number!(Int, i64, i8, i16, i32, i64, isize);
+from_number!(UInt, u64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::from(5_i64), Answer::from(5_usize));
+        assert_ne!(Answer::from(-5_i64), Answer::from(5_u32));
+        assert_ne!(Answer::from(5_u32), Answer::from("5"));
+        assert_eq!(Answer::from(()), Answer::None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for answer in [
+            Answer::from(-42),
+            Answer::from(u64::MAX),
+            Answer::from("Merry Christmas!"),
+            Answer::None,
+        ] {
+            assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer);
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Helpers shared between the solutions of different days.
+
+pub mod cycle;
+pub mod grid;
+pub mod history;
+pub mod math;
+pub mod range;
@@ -0,0 +1,73 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A sequence of states that eventually repeats.
+///
+/// ```text
+/// s0 -> s1 -> s2 -> s3 -> s4 -> s2 ...
+///             |-- cycle --|
+/// start = 2, length = 3
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cycle<T> {
+    /// Every state seen until the first repetition, `history[0]` is the initial state.
+    pub history: Vec<T>,
+    /// Index of the first state that is part of the cycle.
+    pub start: usize,
+    /// Number of states in the cycle.
+    pub length: usize,
+}
+
+impl<T> Cycle<T> {
+    /// Returns the state after `n` steps, without running the steps.
+    pub fn nth(&self, n: usize) -> &T {
+        if n < self.start {
+            &self.history[n]
+        } else {
+            &self.history[self.start + (n - self.start) % self.length]
+        }
+    }
+}
+
+/// Runs `step` from `initial` until a state repeats.
+///
+/// States are stored in a hash map, so this only terminates for sequences with
+/// a finite number of states, like the tilted platforms of day 14.
+pub fn detect_cycle<T, F>(initial: T, mut step: F) -> Cycle<T>
+where
+    T: Hash + Eq + Clone,
+    F: FnMut(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = history.len() - start;
+            return Cycle {
+                history,
+                start,
+                length,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), history.len());
+        history.push(state);
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 ...
+        let cycle = detect_cycle(0, |v| if *v == 4 { 2 } else { v + 1 });
+        assert_eq!(cycle.start, 2);
+        assert_eq!(cycle.length, 3);
+        assert_eq!(*cycle.nth(1), 1);
+        assert_eq!(*cycle.nth(5), 2);
+        assert_eq!(*cycle.nth(1_000_000_000), 4);
+    }
+}
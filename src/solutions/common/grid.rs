@@ -0,0 +1,244 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::solutions::error::{parse_chars, ParseError};
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangle of cells, stored row after row and addressed by `(x, y)` from the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Parses one cell per character of each line with `parse`, the error pointing at the
+    /// first character it rejects or at the first line of a different length.
+    ///
+    /// Empty lines at the end are ignored, like the newline an editor adds, but a grid
+    /// without any line is an error.
+    pub fn from_str(
+        input: &str,
+        message: &str,
+        mut parse: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            let start = cells.len();
+            for cell in parse_chars(line, message, &mut parse) {
+                cells.push(cell?);
+            }
+            let length = cells.len() - start;
+            let expected = *width.get_or_insert(length);
+            if length != expected {
+                return Err(ParseError::new(
+                    line,
+                    format!("Expected {} cells", expected),
+                ));
+            }
+            height += 1;
+        }
+        if height == 0 {
+            return Err(ParseError::new(&input[input.len()..], "Empty grid"));
+        }
+        Ok(Self {
+            cells,
+            width: width.unwrap_or_default(),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at `(x, y)`, or `None` outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// The positions above, left, right and below `(x, y)` that are in the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &NEIGHBOURS4)
+    }
+
+    /// The positions around `(x, y)` that are in the grid, diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &NEIGHBOURS8)
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            self.index_of(x, y).map(|_| (x, y))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // An empty grid has no cells, whatever the chunk size.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} out of the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// All the positions of the grid, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// A grid of the same size, each cell computed from its position and the cell here.
+    pub fn map<U>(&self, mut f: impl FnMut((usize, usize), &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self
+                .positions()
+                .zip(&self.cells)
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) out of the grid", x, y))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) out of the grid", x, y))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::from_str(input, "Invalid digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(digits("123\n456\n\n\r\n").unwrap(), grid);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        let input = "123\n4x6";
+        let error = digits(input).unwrap_err().locate(1, input);
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+        let error = digits("123\n45").unwrap_err();
+        assert_eq!(error.message, "Expected 3 cells");
+
+        assert_eq!(digits("\n").unwrap_err().message, "Empty grid");
+    }
+
+    #[test]
+    fn test_get() {
+        let grid = digits("12\n34").unwrap();
+        assert_eq!(grid.get(1, 1), Some(&4));
+        // Out of the row, not wrapping to the next one.
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789").unwrap();
+        let values = |positions: Vec<(usize, usize)>| {
+            positions.into_iter().map(|p| grid[p]).collect::<Vec<_>>()
+        };
+        assert_eq!(values(grid.neighbours4(1, 1).collect()), [2, 4, 6, 8]);
+        assert_eq!(values(grid.neighbours4(0, 0).collect()), [2, 4]);
+        assert_eq!(
+            values(grid.neighbours8(1, 1).collect()),
+            [1, 2, 3, 4, 6, 7, 8, 9]
+        );
+        assert_eq!(values(grid.neighbours8(2, 2).collect()), [5, 6, 8]);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(
+            grid.positions().collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.map(|(x, _), v| v * 10 + x as u32).row(0), [10, 21, 32]);
+    }
+}
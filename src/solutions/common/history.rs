@@ -0,0 +1,157 @@
+use std::{
+    ops::{Deref, DerefMut},
+    vec,
+};
+
+use crate::solutions::error::{parse_number, ParseError};
+use crate::{visualize_println, Visualizer};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Values(Vec<i64>);
+
+impl Deref for Values {
+    type Target = Vec<i64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Values {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValueHistory {
+    steps: Vec<Values>,
+    firsts: Vec<i64>,
+    lasts: Vec<i64>,
+    pub first: i64,
+    pub last: i64,
+}
+
+impl ValueHistory {
+    pub fn from_str(input: &str) -> Result<Self, ParseError> {
+        let values = input
+            .split_whitespace()
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.len() < 2 {
+            return Err(ParseError::new(input, "Expected at least 2 values"));
+        }
+        Ok(Self::from_values(values))
+    }
+
+    pub fn from_values(values: Vec<i64>) -> Self {
+        let base = Values(values);
+        let first = *base.first().unwrap();
+        let last = *base.last().unwrap();
+        Self {
+            steps: vec![base],
+            firsts: vec![first],
+            lasts: vec![last],
+            first: 0,
+            last: 0,
+        }
+    }
+
+    /// Computes the differences down to zero and extrapolates the previous and next values,
+    /// keeping every step of the difference table only to visualize it.
+    pub fn diff(&mut self, vis: &impl Visualizer) {
+        fn inner_diff(values: &[i64]) -> Values {
+            let mut result = Vec::new();
+            for i in 0..values.len() - 1 {
+                result.push(values[i + 1] - values[i]);
+            }
+            Values(result)
+        }
+
+        let mut base = self.steps.last().unwrap().clone();
+        while base.iter().any(|v| *v != 0) {
+            // A single value left can't be differentiated, it is taken as constant.
+            let diff = if base.len() > 1 {
+                inner_diff(&base)
+            } else {
+                Values(vec![0])
+            };
+            if vis.enabled() {
+                self.steps.push(diff.clone());
+            }
+            self.lasts.push(*diff.last().unwrap());
+            self.firsts.push(*diff.first().unwrap());
+            base = diff;
+        }
+
+        if vis.enabled() {
+            for (i, s) in self.steps.iter().enumerate() {
+                visualize_println!(vis, "step {}: {:?}", i, s);
+            }
+            if let Some(s) = self.steps.last_mut() {
+                s.push(0);
+                s.insert(0, 0);
+            }
+        }
+        let mut current_last = 0;
+        let mut current_first = 0;
+        for i in (1..self.lasts.len()).rev() {
+            let previous_last = self.lasts[i - 1];
+            let previous_first = self.firsts[i - 1];
+            current_last += previous_last;
+            current_first = previous_first - current_first;
+            if vis.enabled() {
+                self.steps[i - 1].push(current_last);
+                self.steps[i - 1].insert(0, current_first);
+            }
+        }
+        self.last = current_last;
+        self.first = current_first;
+
+        for (i, s) in self.steps.iter().enumerate() {
+            visualize_println!(vis, "step {}: {:?}", i, s);
+        }
+    }
+
+    /// Extrapolates the value at index `n`, which can be far past the known values.
+    ///
+    /// Uses Newton's forward difference formula over the first value of each step,
+    /// so `diff` must be called first:
+    ///
+    /// f(n) = f(0) + C(n, 1) * Δf(0) + C(n, 2) * Δ²f(0) + ...
+    ///
+    /// Fails when the value doesn't fit in an `i64`.
+    pub fn nth(&self, n: u64) -> anyhow::Result<i64> {
+        let mut binomial = 1_i128;
+        let mut result = 0_i128;
+        for (k, first) in self.firsts.iter().enumerate() {
+            result += binomial * *first as i128;
+            binomial = binomial * (n as i128 - k as i128) / (k as i128 + 1);
+        }
+        Ok(i64::try_from(result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    #[test]
+    fn test_nth() {
+        let mut history = ValueHistory::from_values(vec![1, 3, 6, 10, 15, 21]);
+        history.diff(&Silent);
+        assert_eq!(history.nth(0).unwrap(), 1);
+        assert_eq!(history.nth(6).unwrap(), 28);
+        assert_eq!(history.nth(99).unwrap(), 5050);
+        assert!(history.nth(1 << 32).is_err());
+    }
+
+    #[test]
+    fn test_not_reaching_zero() {
+        let mut history = ValueHistory::from_str("1 2 4").unwrap();
+        history.diff(&Silent);
+        assert_eq!((history.first, history.last), (1, 7));
+        assert!(ValueHistory::from_str("5").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+/// Calculates the least common multiple (LCM) of two numbers.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// The least common multiple of `a` and `b`.
+#[inline(always)]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    a * b / gcd(a, b)
+}
+
+/// Calculates the greatest common divisor (GCD) of two numbers.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// The GCD of `a` and `b`.
+#[inline(always)]
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 13), 1);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!([2, 3, 4].into_iter().fold(1, lcm), 12);
+    }
+}
@@ -0,0 +1,30 @@
+use std::ops::Range;
+
+/// Returns the overlapping part of two ranges, if any.
+///
+/// ```text
+/// [2..8) & [5..10) -> [5..8)
+/// [2..5) & [5..10) -> None
+/// ```
+pub fn intersect(a: &Range<u64>, b: &Range<u64>) -> Option<Range<u64>> {
+    let start = a.start.max(b.start);
+    let end = a.end.min(b.end);
+    if start < end {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        assert_eq!(intersect(&(2..8), &(5..10)), Some(5..8));
+        assert_eq!(intersect(&(5..10), &(2..8)), Some(5..8));
+        assert_eq!(intersect(&(2..5), &(5..10)), None);
+        assert_eq!(intersect(&(0..10), &(3..4)), Some(3..4));
+    }
+}
@@ -1,15 +1,19 @@
 use anyhow::Result;
 
-use super::Solution;
+use crate::visualize_println;
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
+use super::{Answer, Solution, Visualizer};
+
+fn part1(lines: &[String]) -> Result<u32> {
+    lines
+        .iter()
         .map(|line| {
-            let mut digits = line.chars().filter(|c| c.is_numeric());
-            let first = digits.next().and_then(|v| v.to_digit(10)).unwrap();
-            let last = digits.last().and_then(|v| v.to_digit(10)).unwrap_or(first);
-            first * 10 + last
+            let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+            let Some(first) = digits.next() else {
+                anyhow::bail!("No digit in line: {:?}", line);
+            };
+            let last = digits.next_back().unwrap_or(first);
+            Ok(first * 10 + last)
         })
         .sum()
 }
@@ -29,7 +33,7 @@ fn part1(input: &str) -> u32 {
 /// In this example, the calibration values are 29, 83, 13, 24, 42, 14, and 76. Adding these together produces 281.
 ///
 /// What is the sum of all of the calibration values?
-fn part2(input: &str) -> u32 {
+fn part2(lines: &[String], vis: &impl Visualizer) -> u32 {
     const DIGITS: [&str; 9] = [
         "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
     ];
@@ -46,36 +50,47 @@ fn part2(input: &str) -> u32 {
         })
     }
 
-    fn parse_line(line: &str) -> Option<u32> {
+    fn parse_line(line: &str, vis: &impl Visualizer) -> Option<u32> {
         let range = 0..line.len();
         let first = find_pattern(range.clone(), line)?;
         let last = find_pattern(range.rev(), line)?;
 
         let value = 10 * first + last;
-        if cfg!(feature = "visualize") {
-            println!("{value} => {line}");
-        }
+        visualize_println!(vis, "{value} => {line}");
         Some(value)
     }
 
-    input.lines().filter_map(parse_line).sum()
+    lines
+        .iter()
+        .map(String::as_str)
+        .filter_map(|line| parse_line(line, vis))
+        .sum()
 }
 
 pub struct Day1;
 
 impl Solution for Day1 {
-    fn solve_part1(input: &str) -> Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 1;
+    /// The calibration lines, each part reads the digits differently.
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Parsed, _vis: &impl Visualizer) -> Result<Answer> {
+        Ok(part1(lines)?.into())
     }
 
-    fn solve_part2(input: &str) -> Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(lines: &Self::Parsed, vis: &impl Visualizer) -> Result<Answer> {
+        Ok(part2(lines, vis).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     #[test]
     fn test_part1() {
@@ -83,7 +98,8 @@ mod tests {
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
-        assert_eq!(part1(input), 142);
+        assert_eq!(part1(&Day1::parse(input).unwrap()).unwrap(), 142);
+        assert!(part1(&Day1::parse("1abc2\nabc").unwrap()).is_err());
     }
 
     #[test]
@@ -95,6 +111,6 @@ xtwone3four
 4nineeightseven2
 zoneight234
 7pqrstsixteen";
-        assert_eq!(part2(input), 281);
+        assert_eq!(part2(&Day1::parse(input).unwrap(), &Silent), 281);
     }
 }
@@ -1,44 +1,51 @@
 use crate::visualize_println;
 
-use self::grid::Grid;
+use self::maze::Maze;
 
-use super::Solution;
+use super::{Answer, Silent, Solution, Visualizer};
 
 mod pipe;
-mod grid;
+mod maze;
 
-
-fn part1(input: &str) -> i64 {
-    let mut grid = Grid::from_str(input);
-    visualize_println!("{}", grid);
-    grid.replace_start_with_pipe();
-    let cost = grid.determine_loop_and_cost();
-    grid.cleanup_pipes();
-    visualize_println!("{}", grid);
-    cost as i64
+fn part1(maze: &Maze, vis: &impl Visualizer) -> anyhow::Result<i64> {
+    let mut maze = maze.clone();
+    visualize_println!(vis, "{}", maze);
+    maze.replace_start_with_pipe();
+    let cost = maze.determine_loop_and_cost(vis)?;
+    maze.cleanup_pipes();
+    visualize_println!(vis, "{}", maze);
+    Ok(cost as i64)
 }
 
-fn part2(input: &str) -> i64 {
-    let mut grid = Grid::from_str(input);
-    visualize_println!("{}", grid);
-    grid.replace_start_with_pipe();
-    grid.determine_loop_and_cost();
-    grid.cleanup_pipes();
-    grid.determine_loop_bounds();
-    let inside = grid.calculate_fill();
-    visualize_println!("{}", grid);
-    inside as i64
+fn part2(maze: &Maze, vis: &impl Visualizer) -> anyhow::Result<i64> {
+    let mut maze = maze.clone();
+    visualize_println!(vis, "{}", maze);
+    maze.replace_start_with_pipe();
+    // The walk is only animated in part 1.
+    maze.determine_loop_and_cost(&Silent)?;
+    maze.cleanup_pipes();
+    maze.determine_loop_bounds();
+    let inside = maze.calculate_fill();
+    visualize_println!(vis, "{}", maze);
+    Ok(inside as i64)
 }
 
 pub struct Day10;
 
 impl Solution for Day10 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 10;
+    type Parsed = Maze;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Maze::from_str(input)?)
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part1(maze: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(maze, vis)?.into())
+    }
+
+    fn part2(maze: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(maze, vis)?.into())
     }
 }
 
@@ -50,12 +57,17 @@ mod tests {
     fn test_part1() {
         assert_eq!(
             part1(
-                "-L|F7
+                &Maze::from_str(
+                    "-L|F7
 7S-7|
 L|7||
 -L-J|
 L|-JF"
-            ),
+                )
+                .unwrap(),
+                &Silent
+            )
+            .unwrap(),
             4
         );
     }
@@ -64,19 +76,27 @@ L|-JF"
     fn test_part1_2() {
         assert_eq!(
             part1(
-                "..F7.
+                &Maze::from_str(
+                    "..F7.
 .FJ|.
 SJ.L7
 |F--J
 LJ..."
-            ),
+                )
+                .unwrap(),
+                &Silent
+            )
+            .unwrap(),
             8
         );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2("FF7FSF7F7F7F7F7F---7
+        assert_eq!(
+            part2(
+                &Maze::from_str(
+                    "FF7FSF7F7F7F7F7F---7
 L|LJ||||||||||||F--J
 FL-7LJLJ||||||LJL-77
 F--JF--7||LJLJ7F7FJ-
@@ -85,6 +105,21 @@ L---JF-JLJ.||-FJLJJ7
 |FFJF7L7F-JF7|JL---7
 7-L-JL7||F7|L7F-7F7|
 L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L"), 10);
+L7JLJL-JLJLJL--JLJ.L"
+                )
+                .unwrap(),
+                &Silent
+            )
+            .unwrap(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_start_not_connected() {
+        let error = Maze::from_str(".....\n.S-..\n.....").unwrap_err();
+        assert_eq!(error.message, "The start must connect to two pipes");
+        let maze = Maze::from_str("S-7\n|.|\nL-.").unwrap();
+        assert!(part1(&maze, &Silent).is_err());
     }
 }
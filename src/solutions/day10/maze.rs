@@ -0,0 +1,294 @@
+use std::{collections::HashSet, fmt::Display};
+
+use super::pipe::{Direction, Pipe};
+use crate::solutions::{common::grid::Grid, error::ParseError};
+use crate::{Animation, Step, Visualizer};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fill {
+    Inside,
+    Outside,
+    Pipe,
+}
+
+impl Display for Fill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Fill::Inside => 'I',
+            Fill::Outside => 'O',
+            Fill::Pipe => ' ',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Maze {
+    pipes: Grid<Pipe>,
+    loop_pos: HashSet<(usize, usize)>,
+    cost: Grid<u32>,
+    inside_outside: Grid<Fill>,
+    start: (usize, usize),
+    /// The pipe hidden under the start.
+    start_pipe: Pipe,
+}
+
+impl Maze {
+    pub fn from_str(input: &str) -> Result<Self, ParseError> {
+        let pipes = Grid::from_str(input, "Invalid pipe", |c| match c {
+            'S' => Some(Pipe::Start),
+            '-' => Some(Pipe::Horizontal),
+            '|' => Some(Pipe::Vertical),
+            '.' => Some(Pipe::Ground),
+            'L' => Some(Pipe::BendNE),
+            'J' => Some(Pipe::BendNW),
+            '7' => Some(Pipe::BendSW),
+            'F' => Some(Pipe::BendSE),
+            _ => None,
+        })?;
+        let Some(start) = pipes
+            .positions()
+            .find(|&position| pipes[position] == Pipe::Start)
+        else {
+            return Err(ParseError::new(&input[input.len()..], "Missing the start"));
+        };
+        let Some(start_pipe) = start_pipe(&pipes, start) else {
+            // Every valid character is ASCII, so the column is also the byte offset.
+            let line = input.lines().nth(start.1).unwrap_or(input);
+            return Err(ParseError::new(
+                &line[start.0..start.0 + 1],
+                "The start must connect to two pipes",
+            ));
+        };
+
+        Ok(Maze {
+            loop_pos: HashSet::new(),
+            cost: Grid::filled(pipes.width(), pipes.height(), u32::MAX),
+            inside_outside: Grid::filled(pipes.width(), pipes.height(), Fill::Pipe),
+            pipes,
+            start,
+            start_pipe,
+        })
+    }
+
+    pub fn replace_start_with_pipe(&mut self) {
+        self.pipes[self.start] = self.start_pipe;
+    }
+
+    /// Determine the cost of each pipe in the loop from start
+    ///
+    /// .....    .....
+    /// .S-7.    .012.
+    /// .|.|.    .1.3.
+    /// .L-J.    .234.
+    /// .....    .....
+    ///
+    /// The walk is animated, both ends drawing the pipes they go through in bold.
+    ///
+    /// Fails when the loop leads to a pipe that doesn't connect back.
+    pub fn determine_loop_and_cost(&mut self, vis: &impl Visualizer) -> anyhow::Result<u32> {
+        self.loop_pos.clear();
+        self.loop_pos.insert(self.start);
+        let (x, y) = self.start;
+        self.cost[self.start] = 0;
+        let mut animation = vis.enabled().then(|| {
+            let mut animation =
+                Animation::new("Walking the loop from both ends", self.to_string().lines());
+            animation.push(Step {
+                changes: vec![(x, y, walked(self.pipes[self.start]))],
+                highlights: vec![self.start],
+                caption: "Start".to_string(),
+            });
+            animation
+        });
+        let (mut direction1, mut direction2) = self.pipes[self.start].start_next_pipes();
+        let mut x1 = x;
+        let mut y1 = y;
+        let mut x2 = x;
+        let mut y2 = y;
+        let mut cost = 1;
+        loop {
+            let (node1, node1_pos) = self.pipe(x1, y1, direction1)?;
+            let (node2, node2_pos) = self.pipe(x2, y2, direction2)?;
+            self.loop_pos.insert(node1_pos);
+            self.loop_pos.insert(node2_pos);
+            // println!("{}({}) {}({}) ", node1, direction1, node2, direction2);
+            let (node1_cost, node2_cost) = (self.cost[node1_pos], self.cost[node2_pos]);
+            if node1_cost != u32::MAX && node2_cost != u32::MAX {
+                break;
+            }
+            if node1_cost == u32::MAX {
+                self.cost[node1_pos] = cost;
+            }
+            if node2_cost == u32::MAX {
+                self.cost[node2_pos] = cost;
+            }
+            if let Some(animation) = &mut animation {
+                animation.push(Step {
+                    changes: vec![
+                        (node1_pos.0, node1_pos.1, walked(node1)),
+                        (node2_pos.0, node2_pos.1, walked(node2)),
+                    ],
+                    highlights: vec![node1_pos, node2_pos],
+                    caption: format!("Step {}", cost),
+                });
+            }
+            (direction1, direction2) = (
+                node1.next_direction(direction1),
+                node2.next_direction(direction2),
+            );
+            // println!();
+            (x1, y1) = node1_pos;
+            (x2, y2) = node2_pos;
+            cost += 1;
+        }
+        if let Some(animation) = animation {
+            vis.animate(animation);
+        }
+        Ok(cost - 1)
+    }
+
+    /// The next pipe of the loop, going from `(x, y)` in `towards`.
+    fn pipe(
+        &self,
+        x: usize,
+        y: usize,
+        towards: Direction,
+    ) -> anyhow::Result<(Pipe, (usize, usize))> {
+        let next = match towards {
+            Direction::Up if y == 0 => None,
+            Direction::Left if x == 0 => None,
+            _ => Some(towards.as_indices(x, y)),
+        };
+        match next {
+            Some(next) if connects(&self.pipes, next, (x, y)) => Ok((self.pipes[next], next)),
+            _ => anyhow::bail!("The loop is broken at line {}, column {}", y + 1, x + 1),
+        }
+    }
+
+    /// Removes all pipes that are not part of the loop
+    pub fn cleanup_pipes(&mut self) {
+        for position in self.cost.positions() {
+            if self.cost[position] == u32::MAX {
+                self.pipes[position] = Pipe::Ground;
+            }
+        }
+    }
+
+    /// Determine the bounds of the loop
+    pub fn determine_loop_bounds(&self) -> ((usize, usize), (usize, usize)) {
+        let mut min_x = self.cost.width();
+        let mut min_y = self.cost.height();
+        let mut max_x = 0;
+        let mut max_y = 0;
+        for (x, y) in self.cost.positions() {
+            if self.cost[(x, y)] != u32::MAX {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+        ((min_x, min_y), (max_x, max_y))
+    }
+
+    pub fn calculate_fill(&mut self) -> usize {
+        let ((min_x, min_y), (max_x, max_y)) = self.determine_loop_bounds();
+        let mut inside = 0;
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                if self.loop_pos.contains(&(x, y)) {
+                    continue;
+                }
+                let count = self.pipes.row(y)[x + 1..]
+                    .iter()
+                    .zip(x + 1..)
+                    .filter(|&(pipe, x2)| self.loop_pos.contains(&(x2, y)) && pipe.downwards())
+                    .count();
+                if count % 2 == 1 {
+                    self.inside_outside[(x, y)] = Fill::Inside;
+                    inside += 1;
+                } else {
+                    self.inside_outside[(x, y)] = Fill::Outside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+/// Determine the Start pipe from the neighbours that connect back to it.
+fn start_pipe(pipes: &Grid<Pipe>, start: (usize, usize)) -> Option<Pipe> {
+    let connected = pipes
+        .neighbours4(start.0, start.1)
+        .filter(|&position| connects(pipes, position, start))
+        .map(|position| direction(start, position))
+        .collect::<Vec<_>>();
+    [
+        Pipe::Vertical,
+        Pipe::Horizontal,
+        Pipe::BendNE,
+        Pipe::BendNW,
+        Pipe::BendSE,
+        Pipe::BendSW,
+    ]
+    .into_iter()
+    .find(|pipe| {
+        let (branch1, branch2) = pipe.start_next_pipes();
+        connected.contains(&branch1) && connected.contains(&branch2)
+    })
+}
+
+/// Whether the pipe at `from`, if any, opens towards its neighbour `to`.
+fn connects(pipes: &Grid<Pipe>, from: (usize, usize), to: (usize, usize)) -> bool {
+    match pipes.get(from.0, from.1) {
+        None | Some(Pipe::Start | Pipe::Ground) => false,
+        Some(pipe) => {
+            let (branch1, branch2) = pipe.start_next_pipes();
+            let towards = direction(from, to);
+            branch1 == towards || branch2 == towards
+        }
+    }
+}
+
+/// The direction to go from `from` to its neighbour `to`.
+fn direction(from: (usize, usize), to: (usize, usize)) -> Direction {
+    if to.1 < from.1 {
+        Direction::Up
+    } else if to.1 > from.1 {
+        Direction::Down
+    } else if to.0 < from.0 {
+        Direction::Left
+    } else {
+        Direction::Right
+    }
+}
+
+/// The bold version of a pipe, to tell the walked pipes apart.
+fn walked(pipe: Pipe) -> char {
+    match pipe {
+        Pipe::Vertical => '┃',
+        Pipe::Horizontal => '━',
+        Pipe::BendNE => '┗',
+        Pipe::BendNW => '┛',
+        Pipe::BendSE => '┏',
+        Pipe::BendSW => '┓',
+        Pipe::Start | Pipe::Ground => '*',
+    }
+}
+
+impl Display for Maze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (pipes, fills) in self.pipes.rows().zip(self.inside_outside.rows()) {
+            for (pipe, fill) in pipes.iter().zip(fills) {
+                if *fill != Fill::Pipe {
+                    write!(f, "{}", fill)?;
+                    continue;
+                }
+                write!(f, "{}", pipe)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use std::{fmt::Display, vec};
 
-use super::Solution;
+use super::{common::grid::Grid, error::ParseError, Answer, Solution, Visualizer};
 
 #[derive(Debug, Eq, Clone, Copy)]
 enum Space {
@@ -43,14 +43,6 @@ impl PartialEq for Pair {
 }
 
 impl Space {
-    fn from_char(c: char, x: usize, y: usize) -> Self {
-        match c {
-            '.' => Self::Empty,
-            '#' => Self::Galaxy(x, y),
-            _ => panic!("Invalid space"),
-        }
-    }
-
     fn get_x(&self) -> usize {
         match self {
             Self::Empty => 0,
@@ -75,59 +67,54 @@ impl Display for Space {
     }
 }
 
-#[derive(Debug)]
-struct Universe {
-    grid: Vec<Vec<Space>>,
+#[derive(Debug, Clone)]
+pub struct Universe {
+    grid: Grid<Space>,
     galaxies: Vec<Space>,
     rows: Vec<usize>,
     cols: Vec<usize>,
-    width: usize,
-    #[allow(unused)]
-    height: usize,
 }
 
 impl Universe {
-    fn from_str(input: &str) -> Self {
-        let grid = input
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| Space::from_char(c, x, y))
-                    .collect()
-            })
-            .collect::<Vec<Vec<Space>>>();
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::from_str(input, "Invalid space", |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })?
+        .map(|(x, y), &galaxy| {
+            if galaxy {
+                Space::Galaxy(x, y)
+            } else {
+                Space::Empty
+            }
+        });
         let galaxies = grid
-            .iter()
+            .rows()
             .flatten()
             .filter(|space| matches!(space, Space::Galaxy(_, _)))
             .cloned()
             .collect::<Vec<Space>>();
 
-        let width = grid[0].len();
-        let height = grid.len();
-        Self {
+        Ok(Self {
+            rows: vec![1; grid.height()],
+            cols: vec![1; grid.width()],
             grid,
             galaxies,
-            rows: vec![1; height],
-            cols: vec![1; width],
-            width,
-            height,
-        }
+        })
     }
 
     /// Expands the universe for each row or column that are empty.
     ///
     /// So an empty row causes to duplicate that row. An empty column duplicates that column.
     fn older_expand_universe(&mut self, times: usize) {
-        for (row_index, row) in self.grid.iter().enumerate() {
+        for (row_index, row) in self.grid.rows().enumerate() {
             if self.is_empty_row(row) {
                 self.rows[row_index] = times;
             }
         }
-        for col in (0..self.width).rev() {
-            if self.grid.iter().all(|row| row[col] == Space::Empty) {
+        for col in (0..self.grid.width()).rev() {
+            if self.grid.column(col).all(|space| *space == Space::Empty) {
                 self.cols[col] = times;
             }
         }
@@ -170,32 +157,26 @@ impl Universe {
     /// Calculates the shortest distance between two galaxies.
     /// We can only move up, down, left or right, at each step.
     fn calculate_distance(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> usize {
-        let dx = if x1 > x2 { x1 - x2 } else { x2 - x1 };
-        let dy = if y1 > y2 { y1 - y2 } else { y2 - y1 };
+        let dx = x1.abs_diff(x2);
+        let dy = y1.abs_diff(y2);
         dx + dy
     }
 }
 
 impl Display for Universe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.grid {
-            for col in row {
-                write!(f, "{}", col)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.grid)
     }
 }
 
-fn part1(input: &str) -> i64 {
-    let mut universe = Universe::from_str(input);
+fn part1(universe: &Universe) -> i64 {
+    let mut universe = universe.clone();
     universe.older_expand_universe(2);
     universe.calculate_shortest_paths() as i64
 }
 
-fn part2(input: &str) -> i64 {
-    let mut universe = Universe::from_str(input);
+fn part2(universe: &Universe) -> i64 {
+    let mut universe = universe.clone();
     universe.older_expand_universe(1000000);
     universe.calculate_shortest_paths() as i64
 }
@@ -203,12 +184,19 @@ fn part2(input: &str) -> i64 {
 pub struct Day11;
 
 impl Solution for Day11 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 11;
+    type Parsed = Universe;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Universe::from_str(input)?)
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part1(universe: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(universe).into())
+    }
+
+    fn part2(universe: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(universe).into())
     }
 }
 
@@ -220,7 +208,8 @@ mod tests {
     fn test_part1() {
         assert_eq!(
             part1(
-                "...#......
+                &Universe::from_str(
+                    "...#......
 .......#..
 #.........
 ..........
@@ -230,6 +219,8 @@ mod tests {
 ..........
 .......#..
 #...#....."
+                )
+                .unwrap()
             ),
             374
         );
@@ -248,7 +239,8 @@ mod tests {
 ..........
 .......#..
 #...#.....",
-        );
+        )
+        .unwrap();
         println!("{}", universe);
         println!("expanding...");
         universe.older_expand_universe(10);
@@ -270,7 +262,8 @@ mod tests {
 ..........
 .......#..
 #...#.....",
-        );
+        )
+        .unwrap();
         universe.older_expand_universe(100);
         println!("{}", universe);
         let sum = universe.calculate_shortest_paths();
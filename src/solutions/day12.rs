@@ -1,13 +1,231 @@
-use super::Solution;
+use std::fmt::Display;
+
+use crate::visualize_println;
+
+use super::{
+    error::{parse_chars, parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl Spring {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Spring::Operational),
+            '#' => Some(Spring::Damaged),
+            '?' => Some(Spring::Unknown),
+            _ => None,
+        }
+    }
+
+    fn can_be_damaged(&self) -> bool {
+        matches!(self, Spring::Damaged | Spring::Unknown)
+    }
+
+    fn can_be_operational(&self) -> bool {
+        matches!(self, Spring::Operational | Spring::Unknown)
+    }
+}
+
+impl Display for Spring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Spring::Operational => '.',
+            Spring::Damaged => '#',
+            Spring::Unknown => '?',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+
+impl Record {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let Some((springs, groups)) = input.split_once(' ') else {
+            return Err(ParseError::new(input, "Invalid record"));
+        };
+        let springs =
+            parse_chars(springs, "Invalid spring", Spring::from_char).collect::<Result<_, _>>()?;
+        let groups = groups
+            .split(',')
+            .map(|g| parse_number(g.trim()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { springs, groups })
+    }
+
+    /// Unfolds the record, repeating the springs `times` times joined by an
+    /// unknown spring, and repeating the groups `times` times.
+    fn unfold(&self, times: usize) -> Self {
+        let mut springs = Vec::with_capacity(self.springs.len() * times + times - 1);
+        for i in 0..times {
+            if i > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+        let groups = self.groups.repeat(times);
+        Self { springs, groups }
+    }
+
+    /// Counts the possible arrangements of damaged springs that match the groups.
+    ///
+    /// This is a bottom-up memoization over `(spring index, group index)`, where
+    /// `table[i][j]` is the number of arrangements for `springs[i..]` that match `groups[j..]`.
+    /// Each cell is filled from the cells to the right, so every state is solved once,
+    /// which keeps the unfolded part 2 records at `O(springs * groups)` instead of exponential.
+    ///
+    /// ```text
+    /// ???.### 1,1,3 -> #.#.### (1 arrangement)
+    /// ```
+    fn count_arrangements(&self) -> u64 {
+        let springs = &self.springs;
+        let groups = &self.groups;
+        let n = springs.len();
+        let m = groups.len();
+
+        // damaged_run[i] is the number of consecutive springs that can be damaged starting at i.
+        let mut damaged_run = vec![0_usize; n + 1];
+        for i in (0..n).rev() {
+            if springs[i].can_be_damaged() {
+                damaged_run[i] = damaged_run[i + 1] + 1;
+            }
+        }
+
+        let mut table = vec![vec![0_u64; m + 1]; n + 2];
+        // With no springs and no groups left there is a single valid arrangement.
+        table[n][m] = 1;
+        table[n + 1][m] = 1;
+        for i in (0..n).rev() {
+            if !springs[i].can_be_operational() {
+                break;
+            }
+            table[i][m] = 1;
+        }
+
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                let mut count = 0;
+                if springs[i].can_be_operational() {
+                    count += table[i + 1][j];
+                }
+                let size = groups[j];
+                if springs[i].can_be_damaged()
+                    && damaged_run[i] >= size
+                    && (i + size == n || springs[i + size].can_be_operational())
+                {
+                    // Skip the group and the operational spring that separates it.
+                    count += table[i + size + 1][j + 1];
+                }
+                table[i][j] = count;
+            }
+        }
+        table[0][0]
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for spring in &self.springs {
+            write!(f, "{}", spring)?;
+        }
+        let groups = self
+            .groups
+            .iter()
+            .map(|g| g.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, " {}", groups)
+    }
+}
+
+fn parse_records(input: &str) -> Result<Vec<Record>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(Record::from_str)
+        .collect()
+}
+
+fn part1(records: &[Record], vis: &impl Visualizer) -> u64 {
+    records
+        .iter()
+        .map(|record| {
+            let count = record.count_arrangements();
+            visualize_println!(vis, "{} -> {}", record, count);
+            count
+        })
+        .sum()
+}
+
+fn part2(records: &[Record], vis: &impl Visualizer) -> u64 {
+    records
+        .iter()
+        .map(|record| {
+            let count = record.unfold(5).count_arrangements();
+            visualize_println!(vis, "{} -> {}", record, count);
+            count
+        })
+        .sum()
+}
 
 pub struct Day12;
 
 impl Solution for Day12 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 12;
+    type Parsed = Vec<Record>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_records(input)?)
+    }
+
+    fn part1(records: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(records, vis).into())
+    }
+
+    fn part2(records: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(records, vis).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_records(TEST_DATA).unwrap(), &Silent), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_records(TEST_DATA).unwrap(), &Silent), 525152);
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_count_arrangements() {
+        let counts = TEST_DATA
+            .lines()
+            .map(|line| Record::from_str(line).unwrap().count_arrangements())
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![1, 4, 1, 1, 4, 10]);
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,247 @@
-use super::Solution;
+use console::Style;
+
+use crate::{visualize_print, visualize_println};
+
+use super::{
+    error::{parse_chars, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reflection {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl Reflection {
+    fn summary(&self) -> usize {
+        match self {
+            Reflection::Vertical(columns) => *columns,
+            Reflection::Horizontal(rows) => 100 * rows,
+        }
+    }
+}
+
+/// A pattern of ash (`.`) and rocks (`#`), stored as bitmasks.
+///
+/// Each row and each column is encoded as a number where a rock is a set bit,
+/// so comparing two lines is a single xor and counting the differences is a `count_ones`.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    rows: Vec<u32>,
+    cols: Vec<u32>,
+}
+
+impl Pattern {
+    /// Reads the lines of a pattern, at most 32 by 32 to fit the bitmasks.
+    fn from_lines(lines: &[&str]) -> Result<Self, ParseError> {
+        let width = lines[0].len();
+        let mut rows = vec![0_u32; lines.len()];
+        let mut cols = vec![0_u32; width];
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width || width > 32 || y >= 32 {
+                return Err(ParseError::new(
+                    line,
+                    "Patterns must be rectangles up to 32x32",
+                ));
+            }
+            let rocks = parse_chars(line, "Invalid input", |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            });
+            for (x, rock) in rocks.enumerate() {
+                if rock? {
+                    rows[y] |= 1 << x;
+                    cols[x] |= 1 << y;
+                }
+            }
+        }
+        Ok(Self { rows, cols })
+    }
+
+    /// Finds the reflection of the pattern, accepting exactly `smudges` differences.
+    ///
+    /// Rows are searched first, then columns.
+    fn find_reflection(&self, smudges: u32) -> Option<Reflection> {
+        find_mirror(&self.rows, smudges)
+            .map(Reflection::Horizontal)
+            .or_else(|| find_mirror(&self.cols, smudges).map(Reflection::Vertical))
+    }
+
+    fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn is_rock(&self, x: usize, y: usize) -> bool {
+        self.rows[y] & (1 << x) != 0
+    }
+
+    /// Draws the pattern with the reflection line in between the mirrored lines.
+    fn visualize(&self, reflection: Option<Reflection>, vis: &impl Visualizer) {
+        let mirror = Style::new().bright().yellow().bold();
+        let rock = Style::new().green();
+        let ash = Style::new().dim();
+        for y in 0..self.height() {
+            if reflection == Some(Reflection::Horizontal(y)) {
+                visualize_println!(vis, "{}", mirror.apply_to("-".repeat(self.width())));
+            }
+            for x in 0..self.width() {
+                if reflection == Some(Reflection::Vertical(x)) {
+                    visualize_print!(vis, "{}", mirror.apply_to('|'));
+                }
+                if self.is_rock(x, y) {
+                    visualize_print!(vis, "{}", rock.apply_to('#'));
+                } else {
+                    visualize_print!(vis, "{}", ash.apply_to('.'));
+                }
+            }
+            visualize_println!(vis);
+        }
+        match reflection {
+            Some(reflection) => {
+                visualize_println!(vis, "{:?} => {}", reflection, reflection.summary())
+            }
+            None => visualize_println!(vis, "No reflection"),
+        }
+        visualize_println!(vis);
+    }
+}
+
+/// Finds the mirror position in a list of lines, returning the number of lines before it.
+///
+/// The mirror is only valid when the lines on both sides differ by exactly `smudges` bits,
+/// so 0 finds the clean reflection and 1 finds the reflection that needs a smudge fixed.
+fn find_mirror(lines: &[u32], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&mirror| {
+        let before = lines[..mirror].iter().rev();
+        let after = lines[mirror..].iter();
+        before
+            .zip(after)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum::<u32>()
+            == smudges
+    })
+}
+
+fn parse_patterns(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    lines
+        .split(|line| line.trim().is_empty())
+        .filter(|block| !block.is_empty())
+        .map(Pattern::from_lines)
+        .collect()
+}
+
+fn summarize(patterns: &[Pattern], smudges: u32, vis: &impl Visualizer) -> anyhow::Result<usize> {
+    patterns
+        .iter()
+        .enumerate()
+        .map(|(i, pattern)| {
+            let reflection = pattern.find_reflection(smudges);
+            if vis.enabled() {
+                pattern.visualize(reflection, vis);
+            }
+            let Some(reflection) = reflection else {
+                anyhow::bail!("No reflection in pattern {}", i + 1);
+            };
+            Ok(reflection.summary())
+        })
+        .sum()
+}
+
+fn part1(patterns: &[Pattern], vis: &impl Visualizer) -> anyhow::Result<usize> {
+    summarize(patterns, 0, vis)
+}
+
+fn part2(patterns: &[Pattern], vis: &impl Visualizer) -> anyhow::Result<usize> {
+    summarize(patterns, 1, vis)
+}
 
 pub struct Day13;
 
 impl Solution for Day13 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 13;
+    type Parsed = Vec<Pattern>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_patterns(input)?)
+    }
+
+    fn part1(patterns: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(patterns, vis)?.into())
+    }
+
+    fn part2(patterns: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(patterns, vis)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            part1(&parse_patterns(TEST_DATA).unwrap(), &Silent).unwrap(),
+            405
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(&parse_patterns(TEST_DATA).unwrap(), &Silent).unwrap(),
+            400
+        );
+    }
+
+    #[test]
+    fn test_no_reflection() {
+        let patterns = parse_patterns("#..\n..#\n.#.").unwrap();
+        assert!(part1(&patterns, &Silent).is_err());
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_find_reflection() {
+        let patterns = parse_patterns(TEST_DATA).unwrap();
+        assert_eq!(
+            patterns[0].find_reflection(0),
+            Some(Reflection::Vertical(5))
+        );
+        assert_eq!(
+            patterns[1].find_reflection(0),
+            Some(Reflection::Horizontal(4))
+        );
+        assert_eq!(
+            patterns[0].find_reflection(1),
+            Some(Reflection::Horizontal(3))
+        );
+        assert_eq!(
+            patterns[1].find_reflection(1),
+            Some(Reflection::Horizontal(1))
+        );
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,216 @@
-use super::Solution;
+use std::fmt::Display;
+
+use crate::visualize_println;
+
+use super::{
+    common::{cycle::detect_cycle, grid::Grid},
+    error::ParseError,
+    Answer, Solution, Visualizer,
+};
+
+const SPIN_CYCLES: usize = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Rock {
+    Round,
+    Cube,
+    Empty,
+}
+
+impl Rock {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'O' => Some(Rock::Round),
+            '#' => Some(Rock::Cube),
+            '.' => Some(Rock::Empty),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Rock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Rock::Round => 'O',
+            Rock::Cube => '#',
+            Rock::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tilt {
+    North,
+    West,
+    South,
+    East,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    grid: Grid<Rock>,
+}
+
+impl Platform {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            grid: Grid::from_str(input, "Invalid rock", Rock::from_char)?,
+        })
+    }
+
+    /// Rolls all the round rocks as far as possible in the tilt direction.
+    ///
+    /// Each line along the tilt is scanned starting at the wall it rolls to,
+    /// keeping track of the next free slot, which is reset after every cube rock.
+    fn tilt(&mut self, tilt: Tilt) {
+        let (lines, length) = match tilt {
+            Tilt::North | Tilt::South => (self.grid.width(), self.grid.height()),
+            Tilt::West | Tilt::East => (self.grid.height(), self.grid.width()),
+        };
+        for line in 0..lines {
+            let mut free = 0;
+            for i in 0..length {
+                let (x, y) = self.position(tilt, line, i);
+                match self.grid[(x, y)] {
+                    Rock::Cube => free = i + 1,
+                    Rock::Round => {
+                        if free != i {
+                            let (fx, fy) = self.position(tilt, line, free);
+                            self.grid[(fx, fy)] = Rock::Round;
+                            self.grid[(x, y)] = Rock::Empty;
+                        }
+                        free += 1;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    /// Maps the `i` position of a `line` scanned from the tilt wall to grid coordinates.
+    fn position(&self, tilt: Tilt, line: usize, i: usize) -> (usize, usize) {
+        match tilt {
+            Tilt::North => (line, i),
+            Tilt::South => (line, self.grid.height() - 1 - i),
+            Tilt::West => (i, line),
+            Tilt::East => (self.grid.width() - 1 - i, line),
+        }
+    }
+
+    /// A spin cycle tilts north, then west, then south, then east.
+    fn spin_cycle(&mut self) {
+        for tilt in [Tilt::North, Tilt::West, Tilt::South, Tilt::East] {
+            self.tilt(tilt);
+        }
+    }
+
+    /// The load of each round rock is the number of rows from it to the south edge.
+    fn north_load(&self) -> usize {
+        self.grid
+            .rows()
+            .enumerate()
+            .map(|(y, row)| {
+                (self.grid.height() - y) * row.iter().filter(|r| **r == Rock::Round).count()
+            })
+            .sum()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+fn part1(platform: &Platform, vis: &impl Visualizer) -> usize {
+    let mut platform = platform.clone();
+    platform.tilt(Tilt::North);
+    visualize_println!(vis, "{}", platform);
+    platform.north_load()
+}
+
+/// Spinning a billion times is not feasible, but the platform settles into a loop,
+/// so we find it and jump straight to the state of the last spin.
+fn part2(platform: &Platform, vis: &impl Visualizer) -> usize {
+    let cycle = detect_cycle(platform.clone(), |platform| {
+        let mut platform = platform.clone();
+        platform.spin_cycle();
+        platform
+    });
+    visualize_println!(
+        vis,
+        "Cycle starts at spin {} and repeats every {} spins",
+        cycle.start,
+        cycle.length
+    );
+    let platform = cycle.nth(SPIN_CYCLES);
+    visualize_println!(vis, "{}", platform);
+    platform.north_load()
+}
 
 pub struct Day14;
 
 impl Solution for Day14 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 14;
+    type Parsed = Platform;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Platform::from_str(input)?)
+    }
+
+    fn part1(platform: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(platform, vis).into())
+    }
+
+    fn part2(platform: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(platform, vis).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&Platform::from_str(TEST_DATA).unwrap(), &Silent), 136);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&Platform::from_str(TEST_DATA).unwrap(), &Silent), 64);
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_spin_cycle() {
+        let mut platform = Platform::from_str(TEST_DATA).unwrap();
+        platform.spin_cycle();
+        assert_eq!(
+            platform.to_string(),
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+        );
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,227 @@
-use super::Solution;
+use console::Style;
+
+use crate::{visualize_print, visualize_println};
+
+use super::{
+    error::{parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+const BOXES: usize = 256;
+
+/// The Holiday ASCII String Helper algorithm.
+///
+/// For each character: add its ASCII code, multiply by 17 and keep the remainder of dividing by 256.
+///
+/// ```text
+/// HASH -> 52
+/// rn=1 -> 30
+/// ```
+fn hash(input: &str) -> u8 {
+    input
+        .bytes()
+        .fold(0_u8, |acc, c| acc.wrapping_add(c).wrapping_mul(17))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation<'a> {
+    Remove(&'a str),
+    Insert(&'a str, u8),
+}
+
+impl<'a> Operation<'a> {
+    fn from_str(step: &'a str) -> Result<Self, ParseError> {
+        if let Some(label) = step.strip_suffix('-') {
+            Ok(Operation::Remove(label))
+        } else if let Some((label, focal_length)) = step.split_once('=') {
+            Ok(Operation::Insert(label, parse_number(focal_length)?))
+        } else {
+            Err(ParseError::new(step, "Invalid step"))
+        }
+    }
+
+    fn label(&self) -> &'a str {
+        match self {
+            Operation::Remove(label) | Operation::Insert(label, _) => label,
+        }
+    }
+}
+
+/// A step of the initialization sequence, kept as text for the hash of part 1.
+pub struct Step {
+    text: String,
+    /// The length of the label at the start of `text`.
+    label_len: usize,
+    /// Only set when inserting a lens.
+    focal_length: Option<u8>,
+}
+
+impl Step {
+    fn from_str(step: &str) -> Result<Self, ParseError> {
+        let operation = Operation::from_str(step)?;
+        let focal_length = match operation {
+            Operation::Remove(_) => None,
+            Operation::Insert(_, focal_length) => Some(focal_length),
+        };
+        Ok(Self {
+            text: step.to_string(),
+            label_len: operation.label().len(),
+            focal_length,
+        })
+    }
+
+    fn operation(&self) -> Operation<'_> {
+        let label = &self.text[..self.label_len];
+        match self.focal_length {
+            Some(focal_length) => Operation::Insert(label, focal_length),
+            None => Operation::Remove(label),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Lens<'a> {
+    label: &'a str,
+    focal_length: u8,
+}
+
+/// The Holiday ASCII String Helper Manual Arrangement Procedure.
+///
+/// Each box keeps its lenses in insertion order, replacing a lens keeps its slot.
+struct LensLibrary<'a> {
+    boxes: Vec<Vec<Lens<'a>>>,
+}
+
+impl<'a> LensLibrary<'a> {
+    fn new() -> Self {
+        Self {
+            boxes: vec![Vec::new(); BOXES],
+        }
+    }
+
+    fn apply(&mut self, operation: Operation<'a>) {
+        let lenses = &mut self.boxes[hash(operation.label()) as usize];
+        let slot = lenses.iter().position(|l| l.label == operation.label());
+        match (operation, slot) {
+            (Operation::Remove(_), Some(slot)) => {
+                lenses.remove(slot);
+            }
+            (Operation::Remove(_), None) => {}
+            (Operation::Insert(_, focal_length), Some(slot)) => {
+                lenses[slot].focal_length = focal_length;
+            }
+            (Operation::Insert(label, focal_length), None) => {
+                lenses.push(Lens {
+                    label,
+                    focal_length,
+                });
+            }
+        }
+    }
+
+    /// The focusing power of a lens is (box + 1) * (slot + 1) * focal length.
+    fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(b, lenses)| {
+                lenses
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, lens)| (b + 1) * (slot + 1) * lens.focal_length as usize)
+            })
+            .sum()
+    }
+
+    /// Prints the non empty boxes, highlighting the box changed by the step.
+    fn visualize(&self, step: &str, operation: Operation<'_>, vis: &impl Visualizer) {
+        let gold = Style::new().bright().yellow().bold();
+        let green = Style::new().green().bold();
+        let dim = Style::new().dim();
+        let changed = hash(operation.label()) as usize;
+
+        visualize_println!(vis, "After \"{}\":", gold.apply_to(step));
+        for (b, lenses) in self.boxes.iter().enumerate() {
+            if lenses.is_empty() {
+                continue;
+            }
+            let style = if b == changed { &green } else { &dim };
+            visualize_print!(vis, "Box {:03}:", style.apply_to(b));
+            for lens in lenses {
+                visualize_print!(vis, " [{} {}]", lens.label, lens.focal_length);
+            }
+            visualize_println!(vis);
+        }
+        visualize_println!(vis);
+    }
+}
+
+fn parse_steps(input: &str) -> Result<Vec<Step>, ParseError> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(Step::from_str)
+        .collect()
+}
+
+fn part1(steps: &[Step]) -> u32 {
+    steps.iter().map(|step| hash(&step.text) as u32).sum()
+}
+
+fn part2(steps: &[Step], vis: &impl Visualizer) -> usize {
+    let mut library = LensLibrary::new();
+    for step in steps {
+        let operation = step.operation();
+        library.apply(operation);
+        if vis.enabled() {
+            library.visualize(&step.text, operation, vis);
+        }
+    }
+    library.focusing_power()
+}
 
 pub struct Day15;
 
 impl Solution for Day15 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 15;
+    type Parsed = Vec<Step>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_steps(input)?)
+    }
+
+    fn part1(steps: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(steps).into())
+    }
+
+    fn part2(steps: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(steps, vis).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn=1"), 30);
+        assert_eq!(hash("rn"), 0);
+        assert_eq!(hash("qp"), 1);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_steps(TEST_DATA).unwrap()), 1320);
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_steps(TEST_DATA).unwrap(), &Silent), 145);
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,291 @@
-use super::Solution;
+use std::{fmt::Display, thread};
+
+use crate::{visualize_print, visualize_println};
+
+use super::{common::grid::Grid, error::ParseError, Answer, Solution, Visualizer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    /// `/`
+    MirrorForward,
+    /// `\`
+    MirrorBackward,
+    /// `|`
+    SplitterVertical,
+    /// `-`
+    SplitterHorizontal,
+}
+
+impl Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Tile::Empty),
+            '/' => Some(Tile::MirrorForward),
+            '\\' => Some(Tile::MirrorBackward),
+            '|' => Some(Tile::SplitterVertical),
+            '-' => Some(Tile::SplitterHorizontal),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Tile::Empty => '.',
+            Tile::MirrorForward => '/',
+            Tile::MirrorBackward => '\\',
+            Tile::SplitterVertical => '|',
+            Tile::SplitterHorizontal => '-',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Bit used to mark the direction in the visited set.
+    fn bit(&self) -> u8 {
+        match self {
+            Direction::Up => 1,
+            Direction::Down => 1 << 1,
+            Direction::Left => 1 << 2,
+            Direction::Right => 1 << 3,
+        }
+    }
+
+    /// Returns the outgoing directions of a beam entering `tile` in this direction.
+    fn through(self, tile: Tile) -> (Direction, Option<Direction>) {
+        use Direction::*;
+        match (tile, self) {
+            (Tile::MirrorForward, Right) => (Up, None),
+            (Tile::MirrorForward, Left) => (Down, None),
+            (Tile::MirrorForward, Up) => (Right, None),
+            (Tile::MirrorForward, Down) => (Left, None),
+            (Tile::MirrorBackward, Right) => (Down, None),
+            (Tile::MirrorBackward, Left) => (Up, None),
+            (Tile::MirrorBackward, Up) => (Left, None),
+            (Tile::MirrorBackward, Down) => (Right, None),
+            (Tile::SplitterVertical, Left | Right) => (Up, Some(Down)),
+            (Tile::SplitterHorizontal, Up | Down) => (Left, Some(Right)),
+            _ => (self, None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Beam {
+    x: usize,
+    y: usize,
+    direction: Direction,
+}
+
+pub struct Contraption {
+    tiles: Grid<Tile>,
+}
+
+impl Contraption {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            tiles: Grid::from_str(input, "Invalid tile", Tile::from_char)?,
+        })
+    }
+
+    /// Moves the beam one tile in its direction, if it doesn't leave the contraption.
+    fn advance(&self, x: usize, y: usize, direction: Direction) -> Option<Beam> {
+        let (x, y) = match direction {
+            Direction::Up if y > 0 => (x, y - 1),
+            Direction::Down if y + 1 < self.tiles.height() => (x, y + 1),
+            Direction::Left if x > 0 => (x - 1, y),
+            Direction::Right if x + 1 < self.tiles.width() => (x + 1, y),
+            _ => return None,
+        };
+        Some(Beam { x, y, direction })
+    }
+
+    /// Traces the beam entering at `start`, returning the energized map.
+    ///
+    /// Every tile keeps a bitmask of the directions beams already went through it,
+    /// so beams caught in a loop between mirrors and splitters are dropped.
+    fn energize(&self, start: Beam) -> Grid<u8> {
+        let mut visited = Grid::filled(self.tiles.width(), self.tiles.height(), 0_u8);
+        let mut beams = vec![start];
+        while let Some(Beam { x, y, direction }) = beams.pop() {
+            if visited[(x, y)] & direction.bit() != 0 {
+                continue;
+            }
+            visited[(x, y)] |= direction.bit();
+            let (first, second) = direction.through(self.tiles[(x, y)]);
+            beams.extend(self.advance(x, y, first));
+            if let Some(second) = second {
+                beams.extend(self.advance(x, y, second));
+            }
+        }
+        visited
+    }
+
+    fn energized_tiles(&self, start: Beam, vis: &impl Visualizer) -> usize {
+        let visited = self.energize(start);
+        if vis.enabled() {
+            self.visualize(&visited, vis);
+        }
+        count_energized(&visited)
+    }
+
+    /// All the beams entering from the edges of the contraption.
+    fn edge_beams(&self) -> Vec<Beam> {
+        let (w, h) = (self.tiles.width(), self.tiles.height());
+        let columns = (0..w).flat_map(|x| {
+            [
+                Beam {
+                    x,
+                    y: 0,
+                    direction: Direction::Down,
+                },
+                Beam {
+                    x,
+                    y: h - 1,
+                    direction: Direction::Up,
+                },
+            ]
+        });
+        let rows = (0..h).flat_map(|y| {
+            [
+                Beam {
+                    x: 0,
+                    y,
+                    direction: Direction::Right,
+                },
+                Beam {
+                    x: w - 1,
+                    y,
+                    direction: Direction::Left,
+                },
+            ]
+        });
+        columns.chain(rows).collect()
+    }
+
+    fn visualize(&self, visited: &Grid<u8>, vis: &impl Visualizer) {
+        let energized = console::Style::new().bright().yellow().bold();
+        for (tiles, visited) in self.tiles.rows().zip(visited.rows()) {
+            for (tile, visited) in tiles.iter().zip(visited) {
+                if *visited != 0 {
+                    visualize_print!(vis, "{}", energized.apply_to(tile));
+                } else {
+                    visualize_print!(vis, "{}", console::style(tile).dim());
+                }
+            }
+            visualize_println!(vis);
+        }
+        visualize_println!(vis);
+    }
+}
+
+fn count_energized(visited: &Grid<u8>) -> usize {
+    visited.rows().flatten().filter(|v| **v != 0).count()
+}
+
+fn part1(contraption: &Contraption, vis: &impl Visualizer) -> usize {
+    contraption.energized_tiles(
+        Beam {
+            x: 0,
+            y: 0,
+            direction: Direction::Right,
+        },
+        vis,
+    )
+}
+
+/// Tries every edge entry point, splitting the starts between the available threads.
+fn part2(contraption: &Contraption, vis: &impl Visualizer) -> usize {
+    let beams = contraption.edge_beams();
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = beams.len().div_ceil(threads).max(1);
+    let best = thread::scope(|scope| {
+        let handles = beams
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|beam| count_energized(&contraption.energize(*beam)))
+                        .max()
+                        .unwrap_or(0)
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("Beam thread panicked"))
+            .max()
+            .unwrap_or(0)
+    });
+    visualize_println!(
+        vis,
+        "Tried {} entry points on {} threads",
+        beams.len(),
+        threads
+    );
+    best
+}
 
 pub struct Day16;
 
 impl Solution for Day16 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 16;
+    type Parsed = Contraption;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Contraption::from_str(input)?)
+    }
+
+    fn part1(contraption: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(contraption, vis).into())
+    }
+
+    fn part2(contraption: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(contraption, vis).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            part1(&Contraption::from_str(TEST_DATA).unwrap(), &Silent),
+            46
+        );
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(&Contraption::from_str(TEST_DATA).unwrap(), &Silent),
+            51
+        );
     }
-}
\ No newline at end of file
+}
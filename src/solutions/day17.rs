@@ -1,13 +1,269 @@
-use super::Solution;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use console::Style;
+
+use crate::visualize_print;
+
+use super::{common::grid::Grid, error::ParseError, Answer, Solution, Visualizer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn index(&self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        }
+    }
+
+    fn turns(&self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+
+    fn arrow(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+/// A crucible in the search, `run` is how many blocks it moved straight in `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct State {
+    x: usize,
+    y: usize,
+    direction: Direction,
+    run: usize,
+}
+
+pub struct City {
+    heat_loss: Grid<u32>,
+}
+
+impl City {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            heat_loss: Grid::from_str(input, "Invalid heat loss", |c| c.to_digit(10))?,
+        })
+    }
+
+    fn step(&self, state: &State, direction: Direction) -> Option<State> {
+        let (x, y) = (state.x, state.y);
+        let (x, y) = match direction {
+            Direction::Up if y > 0 => (x, y - 1),
+            Direction::Down if y + 1 < self.heat_loss.height() => (x, y + 1),
+            Direction::Left if x > 0 => (x - 1, y),
+            Direction::Right if x + 1 < self.heat_loss.width() => (x + 1, y),
+            _ => return None,
+        };
+        let run = if direction == state.direction {
+            state.run + 1
+        } else {
+            1
+        };
+        Some(State {
+            x,
+            y,
+            direction,
+            run,
+        })
+    }
+
+    /// Finds the path with the least heat loss from the top-left to the bottom-right block.
+    ///
+    /// This is Dijkstra over `(position, direction, run)`, so the same block can be visited
+    /// with different momentum. The crucible must move at least `min_run` blocks before
+    /// turning or stopping, and at most `max_run` blocks in a straight line.
+    ///
+    /// # Returns
+    ///
+    /// The heat loss and the path taken, excluding the starting block.
+    fn least_heat_loss(&self, min_run: usize, max_run: usize) -> Option<(u32, Vec<State>)> {
+        let (width, height) = (self.heat_loss.width(), self.heat_loss.height());
+        let states = width * height * 4 * (max_run + 1);
+        let index =
+            |s: &State| (((s.y * width + s.x) * 4) + s.direction.index()) * (max_run + 1) + s.run;
+        let mut cost = vec![u32::MAX; states];
+        let mut previous: Vec<Option<State>> = vec![None; states];
+        let mut queue = BinaryHeap::new();
+
+        // The crucible can start moving right or down.
+        for direction in [Direction::Right, Direction::Down] {
+            let start = State {
+                x: 0,
+                y: 0,
+                direction,
+                run: 0,
+            };
+            cost[index(&start)] = 0;
+            queue.push(Reverse((0, start)));
+        }
+
+        while let Some(Reverse((heat_loss, state))) = queue.pop() {
+            if heat_loss > cost[index(&state)] {
+                continue;
+            }
+            if state.x == width - 1 && state.y == height - 1 && state.run >= min_run {
+                let mut path = vec![state];
+                let mut current = state;
+                while let Some(p) = previous[index(&current)] {
+                    path.push(p);
+                    current = p;
+                }
+                // Drop the starting block, it doesn't count for the heat loss.
+                path.pop();
+                path.reverse();
+                return Some((heat_loss, path));
+            }
+
+            let mut directions = Vec::with_capacity(3);
+            if state.run < max_run {
+                directions.push(state.direction);
+            }
+            if state.run >= min_run || state.run == 0 {
+                directions.extend(state.direction.turns());
+            }
+            for direction in directions {
+                let Some(next) = self.step(&state, direction) else {
+                    continue;
+                };
+                let next_heat_loss = heat_loss + self.heat_loss[(next.x, next.y)];
+                let next_index = index(&next);
+                if next_heat_loss < cost[next_index] {
+                    cost[next_index] = next_heat_loss;
+                    previous[next_index] = Some(state);
+                    queue.push(Reverse((next_heat_loss, next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Prints the heat loss map with the path drawn over it.
+    fn visualize(&self, path: &[State], vis: &impl Visualizer) {
+        let arrow = Style::new().bright().yellow().bold();
+        let mut grid = self
+            .heat_loss
+            .map(|_, v| console::style(v.to_string()).dim().to_string());
+        for state in path {
+            grid[(state.x, state.y)] = arrow.apply_to(state.direction.arrow()).to_string();
+        }
+        visualize_print!(vis, "{}", grid);
+    }
+}
+
+fn solve(
+    city: &City,
+    min_run: usize,
+    max_run: usize,
+    vis: &impl Visualizer,
+) -> anyhow::Result<u32> {
+    let Some((heat_loss, path)) = city.least_heat_loss(min_run, max_run) else {
+        anyhow::bail!("No path reaches the factory");
+    };
+    if vis.enabled() {
+        city.visualize(&path, vis);
+    }
+    Ok(heat_loss)
+}
+
+fn part1(city: &City, vis: &impl Visualizer) -> anyhow::Result<u32> {
+    solve(city, 0, 3, vis)
+}
+
+fn part2(city: &City, vis: &impl Visualizer) -> anyhow::Result<u32> {
+    solve(city, 4, 10, vis)
+}
 
 pub struct Day17;
 
 impl Solution for Day17 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 17;
+    type Parsed = City;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(City::from_str(input)?)
+    }
+
+    fn part1(city: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(city, vis)?.into())
+    }
+
+    fn part2(city: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(city, vis)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            part1(&City::from_str(TEST_DATA).unwrap(), &Silent).unwrap(),
+            102
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(&City::from_str(TEST_DATA).unwrap(), &Silent).unwrap(),
+            94
+        );
+    }
+
+    #[test]
+    fn test_part2_2() {
+        let input = "111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+        assert_eq!(part2(&City::from_str(input).unwrap(), &Silent).unwrap(), 71);
+    }
+
+    #[test]
+    fn test_no_path() {
+        // Too short to move the 4 blocks needed before stopping.
+        assert!(part2(&City::from_str("11").unwrap(), &Silent).is_err());
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_path() {
+        let city = City::from_str(TEST_DATA).unwrap();
+        let (heat_loss, path) = city.least_heat_loss(0, 3).unwrap();
+        let path_loss = path.iter().map(|s| city.heat_loss[(s.x, s.y)]).sum::<u32>();
+        assert_eq!(heat_loss, path_loss);
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,385 @@
-use super::Solution;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use crate::visualize_println;
+
+use super::{
+    common::range::intersect,
+    error::{parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+const START_WORKFLOW: &str = "in";
+/// Each rating goes from 1 to 4000, stored as the half open range `1..4001`.
+const RATINGS: Range<u64> = 1..4001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Category {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        match s {
+            "x" => Ok(Category::X),
+            "m" => Ok(Category::M),
+            "a" => Ok(Category::A),
+            "s" => Ok(Category::S),
+            _ => Err(ParseError::new(s, "Invalid category")),
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Category::X => 0,
+            Category::M => 1,
+            Category::A => 2,
+            Category::S => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl Target {
+    fn from_str(input: &str) -> Self {
+        match input {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Condition {
+    category: Category,
+    greater: bool,
+    value: u64,
+}
+
+impl Condition {
+    fn matches(&self, part: &Part) -> bool {
+        let rating = part.ratings[self.category.index()];
+        if self.greater {
+            rating > self.value
+        } else {
+            rating < self.value
+        }
+    }
+
+    /// The ratings that pass and the ratings that fail the condition.
+    fn split(&self) -> (Range<u64>, Range<u64>) {
+        if self.greater {
+            (self.value + 1..u64::MAX, 0..self.value + 1)
+        } else {
+            (0..self.value, self.value..u64::MAX)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+impl Rule {
+    /// Parses `a<2006:qkq` or a fallback rule like `rfg`.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let Some((condition, target)) = input.split_once(':') else {
+            return Ok(Self {
+                condition: None,
+                target: Target::from_str(input),
+            });
+        };
+        let Some(operator) = condition.find(['<', '>']) else {
+            return Err(ParseError::new(condition, "Invalid condition"));
+        };
+        Ok(Self {
+            condition: Some(Condition {
+                category: Category::from_str(&condition[..operator])?,
+                greater: condition[operator..].starts_with('>'),
+                value: parse_number(&condition[operator + 1..])?,
+            }),
+            target: Target::from_str(target),
+        })
+    }
+
+    /// The text naming the target of the rule `input`.
+    fn target_text(input: &str) -> &str {
+        input.split_once(':').map_or(input, |(_, target)| target)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part {
+    ratings: [u64; 4],
+}
+
+impl Part {
+    /// Parses `{x=787,m=2655,a=1222,s=2876}`.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut ratings = [0; 4];
+        let fields = input.trim_start_matches('{').trim_end_matches('}');
+        for rating in fields.split(',') {
+            let Some((category, value)) = rating.split_once('=') else {
+                return Err(ParseError::new(rating, "Invalid rating"));
+            };
+            ratings[Category::from_str(category)?.index()] = parse_number(value)?;
+        }
+        Ok(Self { ratings })
+    }
+
+    fn total_rating(&self) -> u64 {
+        self.ratings.iter().sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct System {
+    workflows: HashMap<String, Vec<Rule>>,
+}
+
+impl System {
+    /// Parses the workflows, e.g. `px{a<2006:qkq,m>2090:A,rfg}`.
+    ///
+    /// Every workflow sent to by a rule must exist, every workflow must end with a rule
+    /// without condition so a part always goes somewhere, and no workflow can send a part
+    /// back to itself, so a part always ends up accepted or rejected.
+    fn from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
+        let mut workflows = HashMap::new();
+        let mut names = Vec::new();
+        let mut targets = HashMap::new();
+        for line in lines {
+            let Some((name, rules)) = line.trim_end_matches('}').split_once('{') else {
+                return Err(ParseError::new(line, "Invalid workflow"));
+            };
+            let workflow_targets = targets.entry(name).or_insert_with(Vec::new);
+            let rules = rules
+                .split(',')
+                .inspect(|rule| workflow_targets.push(Rule::target_text(rule)))
+                .map(Rule::from_str)
+                .collect::<Result<Vec<_>, _>>()?;
+            if rules.last().is_none_or(|rule| rule.condition.is_some()) {
+                return Err(ParseError::new(
+                    line,
+                    "Missing the last rule without condition",
+                ));
+            }
+            workflows.insert(name.to_string(), rules);
+            names.push(name);
+        }
+        let unknown = names
+            .iter()
+            .flat_map(|name| &targets[name])
+            .find(|&&target| !matches!(target, "A" | "R") && !workflows.contains_key(target));
+        if let Some(target) = unknown {
+            return Err(ParseError::new(target, "Unknown workflow"));
+        }
+        let mut visiting = HashSet::new();
+        let mut done = HashSet::new();
+        for name in names {
+            if let Some(target) = find_cycle(name, &targets, &mut visiting, &mut done) {
+                return Err(ParseError::new(target, "Workflow cycle"));
+            }
+        }
+        Ok(Self { workflows })
+    }
+
+    fn rules(&self, name: &str) -> &[Rule] {
+        // Every target was checked when parsing.
+        &self.workflows[name]
+    }
+
+    /// Runs the part through the workflows, starting at `in`.
+    fn accepts(&self, part: &Part) -> bool {
+        let mut name = START_WORKFLOW;
+        loop {
+            let rule = self
+                .rules(name)
+                .iter()
+                .find(|r| r.condition.is_none_or(|c| c.matches(part)))
+                .unwrap_or_else(|| panic!("No rule matched in workflow: {}", name));
+            match &rule.target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(next) => name = next,
+            }
+        }
+    }
+
+    /// Counts the combinations of ratings accepted from `target`.
+    ///
+    /// Like the day 5 seed ranges, instead of testing every part the ranges of ratings
+    /// are pushed through the rules, splitting them at each condition into the part
+    /// that goes to the rule target and the part that continues to the next rule.
+    fn count_accepted(&self, target: &Target, ranges: [Range<u64>; 4]) -> u64 {
+        match target {
+            Target::Reject => 0,
+            Target::Accept => ranges.iter().map(|r| r.end - r.start).product(),
+            Target::Workflow(name) => {
+                let mut total = 0;
+                let mut remaining = ranges;
+                for rule in self.rules(name) {
+                    let Some(condition) = rule.condition else {
+                        total += self.count_accepted(&rule.target, remaining);
+                        break;
+                    };
+                    let index = condition.category.index();
+                    let (pass, fail) = condition.split();
+                    if let Some(pass) = intersect(&remaining[index], &pass) {
+                        let mut ranges = remaining.clone();
+                        ranges[index] = pass;
+                        total += self.count_accepted(&rule.target, ranges);
+                    }
+                    match intersect(&remaining[index], &fail) {
+                        Some(fail) => remaining[index] = fail,
+                        None => break,
+                    }
+                }
+                total
+            }
+        }
+    }
+}
+
+/// Walks the workflows reachable from `name`, returning the first target that sends back
+/// to a workflow still being walked.
+fn find_cycle<'a>(
+    name: &'a str,
+    targets: &HashMap<&'a str, Vec<&'a str>>,
+    visiting: &mut HashSet<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<&'a str> {
+    if done.contains(name) {
+        return None;
+    }
+    visiting.insert(name);
+    for &target in targets.get(name).into_iter().flatten() {
+        if visiting.contains(target) {
+            return Some(target);
+        }
+        if let Some(cycle) = find_cycle(target, targets, visiting, done) {
+            return Some(cycle);
+        }
+    }
+    visiting.remove(name);
+    done.insert(name);
+    None
+}
+
+fn parse_input(input: &str) -> Result<(System, Vec<Part>), ParseError> {
+    let mut lines = input.lines().map(|line| line.trim());
+    let system = System::from_lines(lines.by_ref().take_while(|line| !line.is_empty()))?;
+    if !system.workflows.contains_key(START_WORKFLOW) {
+        return Err(ParseError::new(
+            &input[input.len()..],
+            "Missing the in workflow",
+        ));
+    }
+    let parts = lines
+        .filter(|line| !line.is_empty())
+        .map(Part::from_str)
+        .collect::<Result<_, _>>()?;
+    Ok((system, parts))
+}
+
+fn part1((system, parts): &(System, Vec<Part>), vis: &impl Visualizer) -> u64 {
+    parts
+        .iter()
+        .filter(|part| {
+            let accepted = system.accepts(part);
+            visualize_println!(
+                vis,
+                "{:?} -> {}",
+                part.ratings,
+                if accepted { "A" } else { "R" }
+            );
+            accepted
+        })
+        .map(|part| part.total_rating())
+        .sum()
+}
+
+fn part2((system, _): &(System, Vec<Part>)) -> u64 {
+    let start = Target::Workflow(START_WORKFLOW.to_string());
+    system.count_accepted(&start, [RATINGS, RATINGS, RATINGS, RATINGS])
+}
 
 pub struct Day19;
 
 impl Solution for Day19 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 19;
+    type Parsed = (System, Vec<Part>);
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(input, vis).into())
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    fn part2(input: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_input(TEST_DATA).unwrap(), &Silent), 19114);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_input(TEST_DATA).unwrap()), 167409079868000);
+    }
+
+    #[test]
+    fn test_invalid_workflows() {
+        let error = parse_input("px{A}\n\n{x=1,m=1,a=1,s=1}").unwrap_err();
+        assert_eq!(error.message, "Missing the in workflow");
+        let error = parse_input("in{x<1:A}").unwrap_err();
+        assert_eq!(error.message, "Missing the last rule without condition");
+
+        let input = "in{a<1:px,R}\npx{x>2:A,in}";
+        let error = parse_input(input).unwrap_err().locate(19, input);
+        assert_eq!((error.line, error.column), (2, 10));
+        assert_eq!(error.message, "Workflow cycle");
+        assert!(parse_input("in{a<1:in,in}").is_err());
     }
-}
\ No newline at end of file
+}
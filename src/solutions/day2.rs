@@ -1,94 +1,97 @@
-use super::Solution;
+use super::{
+    error::{parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
 
 const CUBES: [u32; 3] = [12, 13, 14];
 const CUBES_NAMES: [&str; 3] = ["red", "green", "blue"];
 
-fn part1(input: &str) -> u32 {
-    let mut possible_games = Vec::new();
+/// A game with the number of red, green and blue cubes shown on each set.
+#[derive(Debug)]
+pub struct Game {
+    id: u32,
+    sets: Vec<[u32; 3]>,
+}
 
-    fn validate_game_set(set: &str) -> bool {
-        set.split(',')
-            .filter_map(|s| s.trim().split_once(' ').map(|(n, c)| (n.trim(), c.trim())))
-            .all(|(number, cube_name)| {
-                if let Some(i) = CUBES_NAMES.iter().position(|&name| name == cube_name) {
-                    if let Ok(number) = number.parse::<u32>() {
-                        return number <= CUBES[i];
-                    }
+impl Game {
+    /// Parses `Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green`.
+    fn from_line(line: &str) -> Result<Self, ParseError> {
+        let Some((game, sets)) = line.split_once(':') else {
+            return Err(ParseError::new(line, "Invalid game"));
+        };
+        let Some(id) = game.trim().strip_prefix("Game ") else {
+            return Err(ParseError::new(game, "Invalid game"));
+        };
+        let id = parse_number(id.trim())?;
+        let sets = sets
+            .split(';')
+            .map(|set| {
+                let mut cubes = [0; 3];
+                for cube in set.split(',') {
+                    let Some((number, cube_name)) = cube.trim().split_once(' ') else {
+                        return Err(ParseError::new(cube.trim(), "Invalid cubes"));
+                    };
+                    let cube_name = cube_name.trim();
+                    let Some(i) = CUBES_NAMES.iter().position(|&n| n == cube_name) else {
+                        return Err(ParseError::new(cube_name, "Invalid cube color"));
+                    };
+                    cubes[i] = parse_number(number.trim())?;
                 }
-                false
+                Ok(cubes)
             })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { id, sets })
     }
+}
 
-    for (id, game_line) in input.lines().enumerate() {
-        let Some((game, line)) = game_line.split_once(':') else {
-            continue;
-        };
-        let game = {
-            if !game.starts_with("Game ") {
-                continue;
-            }
-            let game = game[5..].trim();
-            if let Ok(game) = game.parse::<u32>() {
-                if game != id as u32 + 1 {
-                    continue;
+fn part1(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .filter(|game| {
+            game.sets
+                .iter()
+                .all(|set| set.iter().zip(CUBES).all(|(&number, max)| number <= max))
+        })
+        .map(|game| game.id)
+        .sum()
+}
+
+fn part2(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(|game| {
+            let mut min_cubes = [1; 3];
+            for set in &game.sets {
+                for (min, &number) in min_cubes.iter_mut().zip(set) {
+                    *min = (*min).max(number);
                 }
-                game
-            } else {
-                continue;
             }
-        };
-        let line = line.trim();
-        let game_sets = line.split(';');
-
-        if game_sets.map(validate_game_set).all(|v| v) {
-            possible_games.push(game);
-        }
-    }
-    let result = possible_games.iter().sum();
-    result
+            min_cubes.iter().product::<u32>()
+        })
+        .sum()
 }
 
-fn part2(input: &str) -> u32 {
-    let mut power_sum = 0;
+pub struct Day2;
 
-    fn min_power_of_game_set(game: &str) -> u32 {
-        let mut min_cubes = [1; 3];
-        game.split(';').for_each(|set| {
-            set.split(',')
-                .filter_map(|s| s.trim().split_once(' ').map(|(n, c)| (n.trim(), c.trim())))
-                .for_each(|(number, cube_name)| {
-                    if let Some(i) = CUBES_NAMES.iter().position(|&name| name == cube_name) {
-                        if let Ok(number) = number.parse::<u32>() {
-                            if number > min_cubes[i] {
-                                min_cubes[i] = number;
-                            }
-                        }
-                    }
-                })
-        });
-        min_cubes.iter().product()
-    }
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Parsed = Vec<Game>;
 
-    for game_line in input.lines() {
-        let Some((_game, line)) = game_line.split_once(':') else {
-            continue;
-        };
-        let line = line.trim();
-        let min_power_of_game_set = min_power_of_game_set(line);
-        power_sum += min_power_of_game_set;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let games = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(Game::from_line)
+            .collect::<Result<_, _>>()?;
+        Ok(games)
     }
-    power_sum
-}
 
-pub struct Day2;
-
-impl Solution for Day2 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    fn part1(games: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(games).into())
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(games: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(games).into())
     }
 }
 
@@ -104,11 +107,22 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_DATA), 8);
+        assert_eq!(part1(&Day2::parse(TEST_DATA).unwrap()), 8);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_DATA), 2286);
+        assert_eq!(part2(&Day2::parse(TEST_DATA).unwrap()), 2286);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let input = "Game 1: 3 blue\nGame 2: 1 blue, 2 purple";
+        let error = Day2::parse(input).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap().locate(2, input);
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 19, "purple")
+        );
     }
 }
@@ -1,13 +1,307 @@
-use super::Solution;
+use std::collections::{HashMap, VecDeque};
+
+use crate::visualize_println;
+
+use super::{common::math::lcm, error::ParseError, Answer, Solution, Visualizer};
+
+const BROADCASTER: &str = "broadcaster";
+const GOAL: &str = "rx";
+const BUTTON_PRESSES: usize = 1000;
+/// Far more than the periods of a real input, which are a few thousand presses.
+const MAX_PRESSES: u64 = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Broadcaster,
+    /// `%`, flips on a low pulse, remembering if it is on.
+    FlipFlop(bool),
+    /// `&`, remembers the last pulse from each input, indexed like `Module::inputs`.
+    Conjunction(Vec<bool>),
+    /// Modules that only appear as outputs, like `output` or `rx`.
+    Untyped,
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    kind: Kind,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+/// A pulse sent from one module to another, `true` is a high pulse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pulse {
+    from: usize,
+    to: usize,
+    high: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    modules: Vec<Module>,
+    broadcaster: usize,
+}
+
+impl Circuit {
+    /// Parses lines like `%a -> inv, con` into the module graph.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut modules: Vec<Module> = Vec::new();
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let mut index_of = |name: &str, modules: &mut Vec<Module>| {
+            *names.entry(name.to_string()).or_insert_with(|| {
+                modules.push(Module {
+                    name: name.to_string(),
+                    kind: Kind::Untyped,
+                    inputs: Vec::new(),
+                    outputs: Vec::new(),
+                });
+                modules.len() - 1
+            })
+        };
+
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let Some((module, outputs)) = line.split_once(" -> ") else {
+                return Err(ParseError::new(line, "Invalid module"));
+            };
+            let (kind, name) = if let Some(name) = module.strip_prefix('%') {
+                (Kind::FlipFlop(false), name)
+            } else if let Some(name) = module.strip_prefix('&') {
+                (Kind::Conjunction(Vec::new()), name)
+            } else if module == BROADCASTER {
+                (Kind::Broadcaster, module)
+            } else {
+                return Err(ParseError::new(module, "Invalid module type"));
+            };
+            let index = index_of(name, &mut modules);
+            modules[index].kind = kind;
+            for output in outputs.split(',').map(|o| o.trim()) {
+                let output = index_of(output, &mut modules);
+                modules[index].outputs.push(output);
+                modules[output].inputs.push(index);
+            }
+        }
+
+        for module in modules.iter_mut() {
+            if let Kind::Conjunction(memory) = &mut module.kind {
+                *memory = vec![false; module.inputs.len()];
+            }
+        }
+        let broadcaster = index_of(BROADCASTER, &mut modules);
+        Ok(Self {
+            modules,
+            broadcaster,
+        })
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name == name)
+    }
+
+    /// Presses the button once, processing the pulses in the order they were sent.
+    ///
+    /// `on_pulse` is called for every pulse, including the low pulse from the button.
+    fn press_button(&mut self, mut on_pulse: impl FnMut(&Pulse)) {
+        let mut queue = VecDeque::new();
+        queue.push_back(Pulse {
+            from: self.broadcaster,
+            to: self.broadcaster,
+            high: false,
+        });
+        while let Some(pulse) = queue.pop_front() {
+            on_pulse(&pulse);
+            let module = &mut self.modules[pulse.to];
+            let high = match &mut module.kind {
+                Kind::Broadcaster => pulse.high,
+                Kind::FlipFlop(on) => {
+                    if pulse.high {
+                        continue;
+                    }
+                    *on = !*on;
+                    *on
+                }
+                Kind::Conjunction(memory) => {
+                    let input = module
+                        .inputs
+                        .iter()
+                        .position(|i| *i == pulse.from)
+                        .expect("Pulse from unknown input");
+                    memory[input] = pulse.high;
+                    !memory.iter().all(|m| *m)
+                }
+                Kind::Untyped => continue,
+            };
+            queue.extend(module.outputs.iter().map(|&to| Pulse {
+                from: pulse.to,
+                to,
+                high,
+            }));
+        }
+    }
+}
+
+fn part1(circuit: &Circuit, vis: &impl Visualizer) -> u64 {
+    let mut circuit = circuit.clone();
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..BUTTON_PRESSES {
+        circuit.press_button(|pulse| {
+            if pulse.high {
+                high += 1;
+            } else {
+                low += 1;
+            }
+        });
+    }
+    visualize_println!(vis, "Low pulses: {} High pulses: {}", low, high);
+    low * high
+}
+
+/// `rx` is fed by a single conjunction, which only sends a low pulse when all its
+/// inputs last sent a high pulse. Each of those inputs sends a high pulse periodically,
+/// so, like the ghosts of day 8, the first press where they align is the LCM of the periods.
+fn part2(circuit: &Circuit, vis: &impl Visualizer) -> anyhow::Result<u64> {
+    let mut circuit = circuit.clone();
+    let Some(goal) = circuit.find(GOAL) else {
+        anyhow::bail!("Missing the {} module", GOAL);
+    };
+    let [feeder] = circuit.modules[goal].inputs[..] else {
+        anyhow::bail!("Expected a single module feeding {}", GOAL);
+    };
+    if !matches!(circuit.modules[feeder].kind, Kind::Conjunction(_)) {
+        anyhow::bail!("Expected a conjunction feeding {}", GOAL);
+    }
+    let feeder_inputs = circuit.modules[feeder].inputs.clone();
+    let mut periods: Vec<Option<u64>> = vec![None; feeder_inputs.len()];
+    let mut presses = 0;
+    while periods.iter().any(|p| p.is_none()) {
+        if presses == MAX_PRESSES {
+            anyhow::bail!("No period found after {} presses", MAX_PRESSES);
+        }
+        presses += 1;
+        circuit.press_button(|pulse| {
+            if pulse.to != feeder || !pulse.high {
+                return;
+            }
+            if let Some(i) = feeder_inputs.iter().position(|f| *f == pulse.from) {
+                periods[i].get_or_insert(presses);
+            }
+        });
+    }
+    for (input, period) in feeder_inputs.iter().zip(periods.iter()) {
+        visualize_println!(
+            vis,
+            "{} -> {} every {:?} presses",
+            circuit.modules[*input].name,
+            circuit.modules[feeder].name,
+            period
+        );
+    }
+    Ok(periods.into_iter().flatten().fold(1, lcm))
+}
 
 pub struct Day20;
 
 impl Solution for Day20 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 20;
+    type Parsed = Circuit;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Circuit::from_str(input)?)
+    }
+
+    fn part1(circuit: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(circuit, vis).into())
+    }
+
+    fn part2(circuit: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(circuit, vis)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            part1(
+                &Circuit::from_str(
+                    "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a"
+                )
+                .unwrap(),
+                &Silent
+            ),
+            32000000
+        );
+    }
+
+    #[test]
+    fn test_part1_2() {
+        assert_eq!(
+            part1(
+                &Circuit::from_str(
+                    "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output"
+                )
+                .unwrap(),
+                &Silent
+            ),
+            11687500
+        );
+    }
+
+    const COUNTERS: &str = "broadcaster -> a1, b1
+%a1 -> a2
+%a2 -> ainv
+&ainv -> hub
+%b1 -> b2
+%b2 -> b3
+%b3 -> binv
+&binv -> hub
+&hub -> rx";
+
+    #[test]
+    fn test_part2() {
+        // Two flip-flop counters of 2 and 3 bits, feeding `rx` every 4 and 8 presses.
+        assert_eq!(
+            part2(&Circuit::from_str(COUNTERS).unwrap(), &Silent).unwrap(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_part2_feeders() {
+        let circuit = Circuit::from_str("broadcaster -> a, b\n&a -> rx\n&b -> rx").unwrap();
+        assert!(part2(&circuit, &Silent).is_err());
+        // The broadcaster only sends low pulses, so the input of `a` never has a period.
+        let circuit = Circuit::from_str("broadcaster -> a\n&a -> rx").unwrap();
+        assert!(part2(&circuit, &Silent).is_err());
+        let circuit = Circuit::from_str("broadcaster -> a\n&a -> b").unwrap();
+        assert!(part2(&circuit, &Silent).is_err());
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_part2_brute_force() {
+        let mut circuit = Circuit::from_str(COUNTERS).unwrap();
+        let rx = circuit.find(GOAL).unwrap();
+        let mut presses = 0;
+        let mut done = false;
+        while !done {
+            presses += 1;
+            circuit.press_button(|pulse| done |= pulse.to == rx && !pulse.high);
+        }
+        assert_eq!(
+            presses,
+            part2(&Circuit::from_str(COUNTERS).unwrap(), &Silent).unwrap()
+        );
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,212 @@
-use super::Solution;
+use std::collections::VecDeque;
+
+use crate::visualize_println;
+
+use super::{
+    common::{grid::Grid, history::ValueHistory},
+    error::ParseError,
+    Answer, Solution, Visualizer,
+};
+
+const STEPS_PART1: usize = 64;
+const STEPS_PART2: usize = 26_501_365;
+
+#[derive(Debug)]
+pub struct Garden {
+    rocks: Grid<bool>,
+    start: (usize, usize),
+}
+
+impl Garden {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let rocks = Grid::from_str(input, "Invalid tile", |c| match c {
+            '#' => Some(true),
+            '.' | 'S' => Some(false),
+            _ => None,
+        })?;
+        let start = input
+            .lines()
+            .enumerate()
+            .find_map(|(y, line)| line.chars().position(|c| c == 'S').map(|x| (x, y)));
+        let Some(start) = start else {
+            return Err(ParseError::new(&input[input.len()..], "Missing the start"));
+        };
+        Ok(Self { rocks, start })
+    }
+
+    /// Checks for a rock, repeating the map infinitely in every direction.
+    fn is_rock(&self, x: i64, y: i64) -> bool {
+        let x = x.rem_euclid(self.rocks.width() as i64) as usize;
+        let y = y.rem_euclid(self.rocks.height() as i64) as usize;
+        self.rocks[(x, y)]
+    }
+
+    /// Counts the garden plots reachable in exactly `steps` steps.
+    ///
+    /// A plot reached in `n` steps can be reached again in `n + 2` steps by stepping
+    /// back and forth, so after a BFS we count the plots with the same parity as `steps`.
+    /// When `infinite` is set, the map repeats instead of stopping at the edges.
+    fn reachable(&self, steps: usize, infinite: bool) -> usize {
+        let (w, h) = (self.rocks.width() as i64, self.rocks.height() as i64);
+        // A window big enough to hold every plot reachable in `steps` steps.
+        let (margin_x, margin_y) = if infinite {
+            (steps as i64, steps as i64)
+        } else {
+            (0, 0)
+        };
+        let window_width = (w + 2 * margin_x) as usize;
+        let window_height = (h + 2 * margin_y) as usize;
+        let mut distance = vec![usize::MAX; window_width * window_height];
+        let index =
+            |x: i64, y: i64| ((y + margin_y) as usize) * window_width + (x + margin_x) as usize;
+
+        let (sx, sy) = (self.start.0 as i64, self.start.1 as i64);
+        let mut queue = VecDeque::new();
+        distance[index(sx, sy)] = 0;
+        queue.push_back((sx, sy, 0));
+        let mut count = 0;
+        while let Some((x, y, d)) = queue.pop_front() {
+            if d % 2 == steps % 2 {
+                count += 1;
+            }
+            if d == steps {
+                continue;
+            }
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < -margin_x || ny < -margin_y || nx >= w + margin_x || ny >= h + margin_y {
+                    continue;
+                }
+                if self.is_rock(nx, ny) || distance[index(nx, ny)] != usize::MAX {
+                    continue;
+                }
+                distance[index(nx, ny)] = d + 1;
+                queue.push_back((nx, ny, d + 1));
+            }
+        }
+        count
+    }
+
+    /// Counts the plots reachable on the infinite map for a huge number of steps.
+    ///
+    /// The real input has an empty row and column through the start, and the start
+    /// in the center, so the reachable area grows by a full map every `width` steps
+    /// and the count is a quadratic of the number of maps walked. We sample it with
+    /// a BFS and let the day 9 difference table extrapolate the rest.
+    ///
+    /// Fails on a map without that shape, or when the samples don't grow quadratically.
+    fn reachable_extrapolated(&self, steps: usize, vis: &impl Visualizer) -> anyhow::Result<i64> {
+        let size = self.rocks.width();
+        if self.rocks.height() != size || size.is_multiple_of(2) {
+            anyhow::bail!(
+                "Expected a square map of odd size, got {}x{}",
+                size,
+                self.rocks.height()
+            );
+        }
+        if self.start != (size / 2, size / 2) {
+            anyhow::bail!("Expected the start in the center, got {:?}", self.start);
+        }
+        let remainder = steps % size;
+        let samples = (0..4)
+            .map(|i| self.reachable(remainder + i * size, true) as i64)
+            .collect::<Vec<_>>();
+        visualize_println!(vis, "Samples every {} steps: {:?}", size, samples);
+        let third_difference = samples[3] - 3 * samples[2] + 3 * samples[1] - samples[0];
+        if third_difference != 0 {
+            anyhow::bail!("The samples {:?} don't grow quadratically", samples);
+        }
+        let mut history = ValueHistory::from_values(samples);
+        history.diff(vis);
+        history.nth((steps / size) as u64)
+    }
+}
+
+fn part1(garden: &Garden) -> usize {
+    garden.reachable(STEPS_PART1, false)
+}
+
+fn part2(garden: &Garden, vis: &impl Visualizer) -> anyhow::Result<i64> {
+    garden.reachable_extrapolated(STEPS_PART2, vis)
+}
 
 pub struct Day21;
 
 impl Solution for Day21 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 21;
+    type Parsed = Garden;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Garden::from_str(input)?)
+    }
+
+    fn part1(garden: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(garden).into())
+    }
+
+    fn part2(garden: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(garden, vis)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Garden::from_str(TEST_DATA).unwrap().reachable(6, false), 16);
+    }
+
+    #[test]
+    fn test_infinite() {
+        let garden = Garden::from_str(TEST_DATA).unwrap();
+        assert_eq!(garden.reachable(6, true), 16);
+        assert_eq!(garden.reachable(10, true), 50);
+        assert_eq!(garden.reachable(50, true), 1594);
+        assert_eq!(garden.reachable(100, true), 6536);
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_extrapolated() {
+        // An empty garden has the properties of the real input.
+        let input = [".....", ".....", "..S..", ".....", "....."].join("\n");
+        let garden = Garden::from_str(&input).unwrap();
+        for steps in [12, 27, 42] {
+            assert_eq!(
+                garden.reachable_extrapolated(steps, &Silent).unwrap(),
+                garden.reachable(steps, true) as i64
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let input = "...\n.S\n...";
+        let error = Garden::from_str(input).unwrap_err().locate(21, input);
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "Expected 3 cells")
+        );
+
+        for input in ["..\n.S", "...\n.S.", "...\n...\nS.."] {
+            let garden = Garden::from_str(input).unwrap();
+            assert!(garden.reachable_extrapolated(STEPS_PART2, &Silent).is_err());
+        }
+        let garden = Garden::from_str(TEST_DATA).unwrap();
+        assert!(garden.reachable_extrapolated(STEPS_PART2, &Silent).is_err());
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,193 @@
-use super::Solution;
+use std::collections::VecDeque;
+
+use crate::visualize_println;
+
+use super::{
+    error::{parse_numbers, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Brick {
+    start: [usize; 3],
+    end: [usize; 3],
+}
+
+impl Brick {
+    /// Parses `1,0,1~1,2,1`, ordering the ends so `start` is always the lowest corner.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let Some((a, b)) = input.split_once('~') else {
+            return Err(ParseError::new(input, "Invalid brick"));
+        };
+        let (a, b): ([usize; 3], [usize; 3]) = (parse_numbers(a, ',')?, parse_numbers(b, ',')?);
+        Ok(Self {
+            start: [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])],
+            end: [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])],
+        })
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start[0]..=self.end[0])
+            .flat_map(move |x| (self.start[1]..=self.end[1]).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> usize {
+        self.end[2] - self.start[2] + 1
+    }
+}
+
+/// The settled bricks and which bricks rest on which.
+pub struct Stack {
+    bricks: Vec<Brick>,
+    /// `supports[i]` are the bricks resting on top of brick `i`.
+    supports: Vec<Vec<usize>>,
+    /// `supported_by[i]` are the bricks brick `i` rests on.
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Lets the bricks fall, lowest first, keeping a height map of the top brick on each column.
+    fn settle(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort_by_key(|b| b.start[2]);
+        let width = bricks.iter().map(|b| b.end[0]).max().unwrap_or(0) + 1;
+        let depth = bricks.iter().map(|b| b.end[1]).max().unwrap_or(0) + 1;
+        // (height, brick index) of the top of each column.
+        let mut top: Vec<Vec<(usize, Option<usize>)>> = vec![vec![(0, None); depth]; width];
+        let mut supports = vec![Vec::new(); bricks.len()];
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+
+        for i in 0..bricks.len() {
+            let brick = bricks[i];
+            let rest = brick.cells().map(|(x, y)| top[x][y].0).max().unwrap_or(0);
+            for (x, y) in brick.cells() {
+                if let (height, Some(below)) = top[x][y] {
+                    if height == rest && !supported_by[i].contains(&below) {
+                        supported_by[i].push(below);
+                        supports[below].push(i);
+                    }
+                }
+            }
+            let z = rest + 1;
+            bricks[i].end[2] = z + brick.height() - 1;
+            bricks[i].start[2] = z;
+            for (x, y) in brick.cells() {
+                top[x][y] = (bricks[i].end[2], Some(i));
+            }
+        }
+
+        Self {
+            bricks,
+            supports,
+            supported_by,
+        }
+    }
+
+    /// A brick can be disintegrated if every brick on top of it has another support.
+    fn can_disintegrate(&self, brick: usize) -> bool {
+        self.supports[brick]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    /// Counts how many other bricks fall when `brick` is disintegrated.
+    ///
+    /// A brick falls once all the bricks supporting it have fallen, so we walk
+    /// upwards from the removed brick, in the order the bricks settled.
+    fn chain_reaction(&self, brick: usize) -> usize {
+        let mut fallen = vec![false; self.bricks.len()];
+        fallen[brick] = true;
+        let mut queue = VecDeque::from([brick]);
+        let mut count = 0;
+        while let Some(current) = queue.pop_front() {
+            for &above in &self.supports[current] {
+                if fallen[above] {
+                    continue;
+                }
+                if self.supported_by[above].iter().all(|&b| fallen[b]) {
+                    fallen[above] = true;
+                    count += 1;
+                    queue.push_back(above);
+                }
+            }
+        }
+        count
+    }
+}
+
+fn parse_stack(input: &str) -> Result<Stack, ParseError> {
+    let bricks = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(Brick::from_str)
+        .collect::<Result<_, _>>()?;
+    Ok(Stack::settle(bricks))
+}
+
+fn part1(stack: &Stack) -> usize {
+    (0..stack.bricks.len())
+        .filter(|&brick| stack.can_disintegrate(brick))
+        .count()
+}
+
+fn part2(stack: &Stack, vis: &impl Visualizer) -> usize {
+    (0..stack.bricks.len())
+        .map(|brick| {
+            let count = stack.chain_reaction(brick);
+            visualize_println!(vis, "{:?} -> {} bricks fall", stack.bricks[brick], count);
+            count
+        })
+        .sum()
+}
 
 pub struct Day22;
 
 impl Solution for Day22 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 22;
+    type Parsed = Stack;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_stack(input)?)
+    }
+
+    fn part1(stack: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(stack).into())
+    }
+
+    fn part2(stack: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(stack, vis).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_stack(TEST_DATA).unwrap()), 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_stack(TEST_DATA).unwrap(), &Silent), 7);
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_settle() {
+        let stack = parse_stack(TEST_DATA).unwrap();
+        // The vertical brick G falls from z=8 to rest on top of F at z=5.
+        assert_eq!(stack.bricks[6].start[2], 5);
+        assert_eq!(stack.bricks[6].end[2], 6);
+        assert_eq!(stack.supported_by[1], vec![0]);
+        assert_eq!(stack.supports[0], vec![1, 2]);
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,285 @@
-use super::Solution;
+use crate::visualize_println;
+
+use super::{common::grid::Grid, error::ParseError, Answer, Solution, Visualizer};
+
+const DIRECTIONS: [(i64, i64); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Path,
+    Forest,
+    /// A slope can only be walked in its `(dx, dy)` direction.
+    Slope(i64, i64),
+}
+
+impl Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Tile::Path),
+            '#' => Some(Tile::Forest),
+            '^' => Some(Tile::Slope(0, -1)),
+            'v' => Some(Tile::Slope(0, 1)),
+            '<' => Some(Tile::Slope(-1, 0)),
+            '>' => Some(Tile::Slope(1, 0)),
+            _ => None,
+        }
+    }
+}
+
+pub struct Map {
+    tiles: Grid<Tile>,
+    /// The path tiles on the first and last rows.
+    start: (i64, i64),
+    end: (i64, i64),
+}
+
+/// The map compressed to the junctions, `edges[i]` holds `(junction, steps)`.
+struct Graph {
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+}
+
+impl Map {
+    /// Parses the map, which must have an entrance on its first row and an exit on its last.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let tiles = Grid::from_str(input, "Invalid tile", Tile::from_char)?;
+        let gap = |y: usize| {
+            let x = tiles.row(y).iter().position(|t| *t == Tile::Path);
+            let x = x.ok_or_else(|| {
+                let line = input.lines().nth(y).unwrap_or(input);
+                ParseError::new(line, "Missing the entrance or exit")
+            })?;
+            Ok((x as i64, y as i64))
+        };
+        let start = gap(0)?;
+        let end = gap(tiles.height() - 1)?;
+        Ok(Self { tiles, start, end })
+    }
+
+    fn tile(&self, x: i64, y: i64) -> Tile {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => self.tiles.get(x, y).copied().unwrap_or(Tile::Forest),
+            _ => Tile::Forest,
+        }
+    }
+
+    fn open_neighbours(&self, x: i64, y: i64) -> usize {
+        DIRECTIONS
+            .iter()
+            .filter(|(dx, dy)| self.tile(x + dx, y + dy) != Tile::Forest)
+            .count()
+    }
+
+    /// Builds the graph of junctions, the tiles where the path branches.
+    ///
+    /// The long corridors between junctions become weighted edges, which turns
+    /// thousands of tiles into a few dozen nodes. When `slippery` is set, corridors
+    /// that go against a slope are dropped.
+    fn compress(&self, slippery: bool) -> Graph {
+        let mut junctions = vec![self.start, self.end];
+        for (x, y) in self.tiles.positions() {
+            let (x, y) = (x as i64, y as i64);
+            if self.tile(x, y) != Tile::Forest && self.open_neighbours(x, y) > 2 {
+                junctions.push((x, y));
+            }
+        }
+
+        let mut edges = vec![Vec::new(); junctions.len()];
+        for (from, &(jx, jy)) in junctions.iter().enumerate() {
+            for (dx, dy) in DIRECTIONS {
+                let (mut x, mut y) = (jx + dx, jy + dy);
+                let (mut px, mut py) = (jx, jy);
+                let mut steps = 1;
+                let mut valid = true;
+                loop {
+                    let tile = self.tile(x, y);
+                    if tile == Tile::Forest {
+                        valid = false;
+                        break;
+                    }
+                    if let Tile::Slope(sx, sy) = tile {
+                        if slippery && (x - px, y - py) != (sx, sy) {
+                            valid = false;
+                            break;
+                        }
+                    }
+                    if junctions.contains(&(x, y)) {
+                        break;
+                    }
+                    // Follow the corridor, it has a single way forward.
+                    let Some((nx, ny)) =
+                        DIRECTIONS
+                            .iter()
+                            .map(|(dx, dy)| (x + dx, y + dy))
+                            .find(|&(nx, ny)| {
+                                (nx, ny) != (px, py) && self.tile(nx, ny) != Tile::Forest
+                            })
+                    else {
+                        valid = false;
+                        break;
+                    };
+                    (px, py) = (x, y);
+                    (x, y) = (nx, ny);
+                    steps += 1;
+                }
+                if valid {
+                    let to = junctions.iter().position(|&j| j == (x, y)).unwrap();
+                    edges[from].push((to, steps));
+                }
+            }
+        }
+
+        Graph {
+            edges,
+            start: 0,
+            end: 1,
+        }
+    }
+}
+
+impl Graph {
+    /// Finds the longest hike that never steps on the same junction twice.
+    ///
+    /// The junctions fit in a `u64`, so the visited set is a bitmask, which makes
+    /// the exhaustive DFS cheap enough to run over every simple path. Fails on a map
+    /// with more junctions, or when no hike reaches the exit.
+    fn longest_hike(&self) -> anyhow::Result<usize> {
+        if self.edges.len() > 64 {
+            anyhow::bail!("Too many junctions for the bitmask: {}", self.edges.len());
+        }
+        // The exit has a single neighbour, once there, going anywhere else
+        // makes the exit unreachable.
+        let last = match self.edges[self.end][..] {
+            [(last, _)] => {
+                let steps = self.edges[last]
+                    .iter()
+                    .find(|(to, _)| *to == self.end)
+                    .map(|(_, steps)| *steps);
+                steps.map(|steps| (last, steps))
+            }
+            _ => None,
+        };
+        self.dfs(self.start, 1 << self.start, 0, last)
+            .ok_or_else(|| anyhow::anyhow!("No hike reaches the exit"))
+    }
+
+    fn dfs(
+        &self,
+        node: usize,
+        visited: u64,
+        length: usize,
+        last: Option<(usize, usize)>,
+    ) -> Option<usize> {
+        if node == self.end {
+            return Some(length);
+        }
+        if let Some((last, steps)) = last {
+            if node == last {
+                return Some(length + steps);
+            }
+        }
+        self.edges[node]
+            .iter()
+            .filter(|(to, _)| visited & (1 << to) == 0)
+            .filter_map(|&(to, steps)| self.dfs(to, visited | (1 << to), length + steps, last))
+            .max()
+    }
+}
+
+fn longest_hike(map: &Map, slippery: bool, vis: &impl Visualizer) -> anyhow::Result<usize> {
+    let graph = map.compress(slippery);
+    visualize_println!(
+        vis,
+        "Junctions: {} Edges: {}",
+        graph.edges.len(),
+        graph.edges.iter().map(|e| e.len()).sum::<usize>()
+    );
+    graph.longest_hike()
+}
+
+fn part1(map: &Map, vis: &impl Visualizer) -> anyhow::Result<usize> {
+    longest_hike(map, true, vis)
+}
+
+fn part2(map: &Map, vis: &impl Visualizer) -> anyhow::Result<usize> {
+    longest_hike(map, false, vis)
+}
 
 pub struct Day23;
 
 impl Solution for Day23 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 23;
+    type Parsed = Map;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Map::from_str(input)?)
+    }
+
+    fn part1(map: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(map, vis)?.into())
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    fn part2(map: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(map, vis)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            part1(&Map::from_str(TEST_DATA).unwrap(), &Silent).unwrap(),
+            94
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(&Map::from_str(TEST_DATA).unwrap(), &Silent).unwrap(),
+            154
+        );
+    }
+
+    #[test]
+    fn test_no_hike() {
+        let map = Map::from_str("#.#\n###\n#.#").unwrap();
+        assert!(part1(&map, &Silent).is_err());
+
+        // Every open tile is a junction, too many for the bitmask.
+        let mut rows = vec![format!("#.{}", "#".repeat(18))];
+        rows.extend((0..18).map(|_| format!("#{}#", ".".repeat(18))));
+        rows.push(format!("{}.#", "#".repeat(18)));
+        let map = Map::from_str(&rows.join("\n")).unwrap();
+        assert!(part2(&map, &Silent).is_err());
     }
-}
\ No newline at end of file
+}
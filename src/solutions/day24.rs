@@ -1,13 +1,283 @@
-use super::Solution;
+use itertools::Itertools;
+
+use crate::visualize_println;
+
+use super::{
+    error::{parse_numbers, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+const TEST_AREA: (i64, i64) = (200_000_000_000_000, 400_000_000_000_000);
+/// Mersenne prime 2^61 - 1, its products still fit in an `i128`.
+const PRIME: i128 = (1 << 61) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+impl Hailstone {
+    /// Parses `19, 13, 30 @ -2,  1, -2`.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let Some((position, velocity)) = input.split_once('@') else {
+            return Err(ParseError::new(input, "Invalid hailstone"));
+        };
+        Ok(Self {
+            position: parse_numbers(position, ',')?,
+            velocity: parse_numbers(velocity, ',')?,
+        })
+    }
+
+    /// Checks if the paths of two hailstones cross inside the test area, ignoring the z axis.
+    ///
+    /// Solving `p1 + v1 * t = p2 + v2 * s` gives `t = tn / det` and `s = sn / det`.
+    /// Instead of dividing, everything is compared multiplied by `det` in `i128`,
+    /// so there is no rounding, even with positions in the hundreds of trillions.
+    fn crosses_inside(&self, other: &Hailstone, min: i64, max: i64) -> bool {
+        let [x1, y1, _] = self.position.map(|v| v as i128);
+        let [vx1, vy1, _] = self.velocity.map(|v| v as i128);
+        let [x2, y2, _] = other.position.map(|v| v as i128);
+        let [vx2, vy2, _] = other.velocity.map(|v| v as i128);
+
+        let det = vx1 * vy2 - vy1 * vx2;
+        if det == 0 {
+            // Parallel paths never cross.
+            return false;
+        }
+        let tn = (x2 - x1) * vy2 - (y2 - y1) * vx2;
+        let sn = (x2 - x1) * vy1 - (y2 - y1) * vx1;
+        // Make the denominator positive so the comparisons keep their direction.
+        let (det, tn, sn) = if det < 0 {
+            (-det, -tn, -sn)
+        } else {
+            (det, tn, sn)
+        };
+        if tn < 0 || sn < 0 {
+            // The paths crossed in the past.
+            return false;
+        }
+        let x = x1 * det + vx1 * tn;
+        let y = y1 * det + vy1 * tn;
+        let (min, max) = (min as i128 * det, max as i128 * det);
+        (min..=max).contains(&x) && (min..=max).contains(&y)
+    }
+}
+
+fn parse_hailstones(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(Hailstone::from_str)
+        .collect()
+}
+
+fn count_intersections(hailstones: &[Hailstone], min: i64, max: i64) -> usize {
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a.crosses_inside(b, min, max))
+        .count()
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// The three equations a pair of hailstones gives for the rock.
+///
+/// The rock `P + V * t` hits the hailstone `p + v * t` when `(P - p) x (V - v) = 0`.
+/// The only non linear term is `P x V`, which is the same for every hailstone,
+/// so subtracting the equations of two hailstones `i` and `j` leaves:
+///
+/// P x (vj - vi) + (pj - pi) x V = pj x vj - pi x vi
+///
+/// Each row is `[Px, Py, Pz, Vx, Vy, Vz, rhs]`.
+fn pair_equations(i: &Hailstone, j: &Hailstone) -> [[i128; 7]; 3] {
+    let (pi, vi) = (i.position.map(|v| v as i128), i.velocity.map(|v| v as i128));
+    let (pj, vj) = (j.position.map(|v| v as i128), j.velocity.map(|v| v as i128));
+    let a = sub(vj, vi);
+    let b = sub(pj, pi);
+    let c = sub(cross(pj, vj), cross(pi, vi));
+    [
+        [0, a[2], -a[1], 0, -b[2], b[1], c[0]],
+        [-a[2], 0, a[0], b[2], 0, -b[0], c[1]],
+        [a[1], -a[0], 0, -b[1], b[0], 0, c[2]],
+    ]
+}
+
+fn mod_pow(mut base: i128, mut exponent: i128) -> i128 {
+    let mut result = 1;
+    base = base.rem_euclid(PRIME);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % PRIME;
+        }
+        base = base * base % PRIME;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Solves the linear system with Gauss-Jordan elimination modulo a prime.
+///
+/// All the arithmetic stays below `PRIME^2`, so nothing overflows no matter how big the
+/// coordinates are, and since the real solution is made of integers smaller than half the
+/// prime, the modular solution mapped to `(-PRIME/2, PRIME/2]` is the exact solution.
+fn solve_modular(mut rows: Vec<[i128; 7]>) -> Option<[i128; 6]> {
+    for row in rows.iter_mut() {
+        for value in row.iter_mut() {
+            *value = value.rem_euclid(PRIME);
+        }
+    }
+    for column in 0..6 {
+        let pivot = (column..rows.len()).find(|&r| rows[r][column] != 0)?;
+        rows.swap(column, pivot);
+        let inverse = mod_pow(rows[column][column], PRIME - 2);
+        for value in rows[column].iter_mut() {
+            *value = *value * inverse % PRIME;
+        }
+        let pivot_row = rows[column];
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r == column || factor == 0 {
+                continue;
+            }
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row) {
+                *value = (*value - factor * pivot_value).rem_euclid(PRIME);
+            }
+        }
+    }
+    let mut solution = [0; 6];
+    for (i, value) in solution.iter_mut().enumerate() {
+        let v = rows[i][6];
+        *value = if v > PRIME / 2 { v - PRIME } else { v };
+    }
+    Some(solution)
+}
+
+/// Finds the position and velocity of a rock that hits every hailstone.
+///
+/// Two pairs of hailstones give the six equations, other pairs are tried if the
+/// system turns out to be singular or the solution doesn't hit every hailstone.
+fn throw_rock(hailstones: &[Hailstone]) -> Option<([i128; 3], [i128; 3])> {
+    let first = hailstones.first()?;
+    hailstones[1..]
+        .iter()
+        .tuple_combinations()
+        .find_map(|(j, k)| {
+            let rows = pair_equations(first, j)
+                .into_iter()
+                .chain(pair_equations(first, k))
+                .collect();
+            let solution = solve_modular(rows)?;
+            let position = [solution[0], solution[1], solution[2]];
+            let velocity = [solution[3], solution[4], solution[5]];
+            let hits_all = hailstones.iter().all(|h| {
+                let p = sub(position, h.position.map(|v| v as i128));
+                let v = sub(velocity, h.velocity.map(|v| v as i128));
+                cross(p, v) == [0, 0, 0]
+            });
+            hits_all.then_some((position, velocity))
+        })
+}
+
+fn part1(hailstones: &[Hailstone]) -> usize {
+    count_intersections(hailstones, TEST_AREA.0, TEST_AREA.1)
+}
+
+fn part2(hailstones: &[Hailstone], vis: &impl Visualizer) -> anyhow::Result<i64> {
+    let Some((position, velocity)) = throw_rock(hailstones) else {
+        anyhow::bail!("No rock hits every hailstone");
+    };
+    visualize_println!(vis, "Rock {:?} @ {:?}", position, velocity);
+    let sum = position.iter().sum::<i128>();
+    i64::try_from(sum).map_err(|_| anyhow::anyhow!("The rock position sum {} overflows", sum))
+}
 
 pub struct Day24;
 
 impl Solution for Day24 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 24;
+    type Parsed = Vec<Hailstone>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_hailstones(input)?)
+    }
+
+    fn part1(hailstones: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(hailstones).into())
+    }
+
+    fn part2(hailstones: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(hailstones, vis)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_part1() {
+        let hailstones = parse_hailstones(TEST_DATA).unwrap();
+        assert_eq!(count_intersections(&hailstones, 7, 27), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(&parse_hailstones(TEST_DATA).unwrap(), &Silent).unwrap(),
+            47
+        );
+        let single = parse_hailstones("19, 13, 30 @ -2, 1, -2").unwrap();
+        assert!(part2(&single, &Silent).is_err());
+    }
+
+    #[test]
+    fn test_throw_rock() {
+        let hailstones = parse_hailstones(TEST_DATA).unwrap();
+        assert_eq!(throw_rock(&hailstones), Some(([24, 13, 10], [-3, 1, 2])));
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_throw_rock_large() {
+        // Positions in the real input range, where f64 loses precision.
+        let rock = Hailstone {
+            position: [
+                287_430_900_118_581,
+                366_880_453_788_327,
+                171_133_039_204_975,
+            ],
+            velocity: [-27, 113, 213],
+        };
+        let hailstones = [(3, [-91, 40, 17]), (7, [59, -212, 8]), (11, [131, 9, -187])]
+            .iter()
+            .map(|(t, velocity)| {
+                let hit = |i: usize| rock.position[i] + (rock.velocity[i] - velocity[i]) * t;
+                Hailstone {
+                    position: [hit(0), hit(1), hit(2)],
+                    velocity: *velocity,
+                }
+            })
+            .collect::<Vec<_>>();
+        let (position, velocity) = throw_rock(&hailstones).unwrap();
+        assert_eq!(position, rock.position.map(|v| v as i128));
+        assert_eq!(velocity, rock.velocity.map(|v| v as i128));
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,199 @@
-use super::Solution;
+use std::collections::{HashMap, VecDeque};
+
+use crate::visualize_println;
+
+use super::{error::ParseError, Answer, Solution, Visualizer};
+
+const CUT_SIZE: usize = 3;
+
+pub struct Wiring {
+    names: Vec<String>,
+    connections: Vec<Vec<usize>>,
+}
+
+impl Wiring {
+    /// Parses lines like `jqt: rhn xhk nvd`, connections go both ways.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut names = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        let mut connections: Vec<Vec<usize>> = Vec::new();
+        let mut index_of = |name: &str, connections: &mut Vec<Vec<usize>>| {
+            *indices.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                connections.push(Vec::new());
+                connections.len() - 1
+            })
+        };
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let Some((component, others)) = line.split_once(':') else {
+                return Err(ParseError::new(line, "Invalid connections"));
+            };
+            let component = index_of(component.trim(), &mut connections);
+            for other in others.split_ascii_whitespace() {
+                let other = index_of(other, &mut connections);
+                connections[component].push(other);
+                connections[other].push(component);
+            }
+        }
+        Ok(Self { names, connections })
+    }
+
+    /// Finds a path from `source` to `sink` that still has spare capacity, using BFS.
+    ///
+    /// Every wire has a capacity of one in each direction, `flow` holds the wires in use.
+    fn augmenting_path(
+        &self,
+        source: usize,
+        sink: usize,
+        flow: &HashMap<(usize, usize), i32>,
+    ) -> Option<Vec<usize>> {
+        let mut previous = vec![usize::MAX; self.connections.len()];
+        previous[source] = source;
+        let mut queue = VecDeque::from([source]);
+        while let Some(current) = queue.pop_front() {
+            if current == sink {
+                let mut path = vec![sink];
+                let mut node = sink;
+                while node != source {
+                    node = previous[node];
+                    path.push(node);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &next in &self.connections[current] {
+                let used = flow.get(&(current, next)).copied().unwrap_or(0);
+                if previous[next] == usize::MAX && used < 1 {
+                    previous[next] = current;
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Tries to separate `source` from `sink` by cutting `cut_size` wires.
+    ///
+    /// By the max-flow min-cut theorem, if only `cut_size` wire-disjoint paths exist
+    /// between them, those wires are the cut, and the components still reachable from
+    /// `source` without them form one of the groups. Returns the size of that group.
+    fn separate(&self, source: usize, sink: usize, cut_size: usize) -> Option<usize> {
+        let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+        for _ in 0..cut_size {
+            let path = self.augmenting_path(source, sink, &flow)?;
+            for pair in path.windows(2) {
+                *flow.entry((pair[0], pair[1])).or_insert(0) += 1;
+                *flow.entry((pair[1], pair[0])).or_insert(0) -= 1;
+            }
+        }
+        if self.augmenting_path(source, sink, &flow).is_some() {
+            // Same group, there are more paths than wires to cut.
+            return None;
+        }
+
+        let mut reachable = vec![false; self.connections.len()];
+        reachable[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(current) = queue.pop_front() {
+            for &next in &self.connections[current] {
+                let used = flow.get(&(current, next)).copied().unwrap_or(0);
+                if !reachable[next] && used < 1 {
+                    reachable[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        Some(reachable.iter().filter(|r| **r).count())
+    }
+
+    /// Splits the components in two groups by cutting `cut_size` wires.
+    ///
+    /// The first component is in one of the groups, so we look for a component
+    /// in the other group, which is the first one that can be separated from it.
+    ///
+    /// # Returns
+    ///
+    /// The sizes of the two groups.
+    fn split(&self, cut_size: usize, vis: &impl Visualizer) -> Option<(usize, usize)> {
+        let total = self.connections.len();
+        (1..total).find_map(|sink| {
+            let group = self.separate(0, sink, cut_size)?;
+            visualize_println!(
+                vis,
+                "Separated {} from {}: {} | {}",
+                self.names[0],
+                self.names[sink],
+                group,
+                total - group
+            );
+            Some((group, total - group))
+        })
+    }
+}
+
+fn part1(wiring: &Wiring, vis: &impl Visualizer) -> anyhow::Result<usize> {
+    let Some((a, b)) = wiring.split(CUT_SIZE, vis) else {
+        anyhow::bail!("No {} wires split the components in two groups", CUT_SIZE);
+    };
+    Ok(a * b)
+}
 
 pub struct Day25;
 
 impl Solution for Day25 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    const DAY: u32 = 25;
+    type Parsed = Wiring;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Wiring::from_str(input)?)
+    }
+
+    fn part1(wiring: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(wiring, vis)?.into())
+    }
+
+    /// Day 25 has no puzzle for part 2, the star comes from finishing all the others.
+    fn part2(_wiring: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(Answer::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Silent;
+
+    const TEST_DATA: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            part1(&Wiring::from_str(TEST_DATA).unwrap(), &Silent).unwrap(),
+            54
+        );
+    }
+
+    #[test]
+    fn test_no_cut() {
+        // Every component is connected to the 4 others.
+        let wiring = Wiring::from_str("a: b c d e\nb: c d e\nc: d e\nd: e").unwrap();
+        assert!(part1(&wiring, &Silent).is_err());
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        todo!()
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day25::solve_part2(TEST_DATA).unwrap(), Answer::None);
     }
-}
\ No newline at end of file
+}
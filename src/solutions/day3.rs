@@ -3,263 +3,176 @@ use std::ops::RangeInclusive;
 use console::Style;
 use itertools::Itertools;
 
-use super::Solution;
+use crate::{visualize_print, visualize_println};
 
-const SYMBOLS: &str = "!\"#$%&/()=?@{[]}'?«»<>|\\*+~^;,:-";
-const DIRECTIONS: [(i32, i32); 8] = [
-    (-1, 0),
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-];
+use super::{common::grid::Grid, error::ParseError, Answer, Solution, Visualizer};
 
-fn part1(input: &str) -> u32 {
-    let height = input.lines().count() as i32;
-    let width = input.lines().next().unwrap().len() as i32;
-    let input = input
-        .lines()
-        .flat_map(|line| line.as_bytes())
-        .collect::<Vec<_>>();
-    let mut numbers: Vec<u32> = Vec::new();
+const SYMBOLS: &str = "!\"#$%&/()=?@{[]}'?«»<>|\\*+~^;,:-";
 
-    for y in 0..height {
-        let mut numbers_in_line = Vec::new();
-        let mut near_symbol = false;
-        for x in 0..width {
-            let i = (y * height + x) as usize;
-            let Some(&c) = input.get(i) else {
-                continue;
-            };
-            if *c == b'.' {
-                if near_symbol && !numbers_in_line.is_empty() {
-                    build_number(&numbers_in_line, &mut numbers);
-                }
-                numbers_in_line.clear();
-                near_symbol = false;
-            } else if SYMBOLS.contains(*c as char) {
-                if !numbers_in_line.is_empty() {
-                    build_number(&numbers_in_line, &mut numbers);
-                }
-                numbers_in_line.clear();
-            } else if c.is_ascii_digit() {
-                numbers_in_line.push((c - b'0') as u32);
-                if !near_symbol && search_symbol(x, y, &input, height) {
-                    near_symbol = true;
-                }
-            }
-        }
-        if !numbers_in_line.is_empty() && near_symbol {
-            build_number(&numbers_in_line, &mut numbers);
-        }
-    }
-    numbers.iter().sum()
-}
+/// The engine schematic, one character per cell.
+pub type Schematic = Grid<char>;
 
-fn search_symbol(x: i32, y: i32, input: &[&u8], height: i32) -> bool {
-    let (x, y) = (x, y);
-    DIRECTIONS
-        .iter()
-        .map(|(dx, dy)| (x + dx, y + dy))
-        .map(|(nx, ny)| (ny * height + nx) as usize)
-        .filter_map(|i| input.get(i))
-        .filter(|&&c| SYMBOLS.contains(*c as char))
-        .count()
-        > 0
+/// Every character is a cell, only the rows must all have the same length.
+fn parse_schematic(input: &str) -> Result<Schematic, ParseError> {
+    Grid::from_str(input, "Invalid cell", Some)
 }
 
-fn build_number(numbers_in_line: &[u32], numbers: &mut Vec<u32>) -> u32 {
-    let max = numbers_in_line.len() - 1;
-    let value = numbers_in_line
-        .iter()
-        .enumerate()
-        .map(|(i, v)| 10_u32.pow((max - i) as u32) * v)
-        .sum::<u32>();
-    numbers.push(value);
-    value
+/// A number of the schematic, spread over consecutive columns of a row.
+#[derive(Clone, Debug, PartialEq)]
+struct Number {
+    value: u32,
+    y: usize,
+    columns: RangeInclusive<usize>,
 }
 
-fn part2(input: &str) -> u32 {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-    let input = input
-        .lines()
-        .flat_map(|line| line.as_bytes())
-        .collect::<Vec<_>>();
-
-    let mut numbers: Vec<Number> = Vec::new();
-    let gears_positions = input
-        .iter()
-        .enumerate()
-        .filter_map(|(i, &c)| if *c == b'*' { Some(i) } else { None })
-        .collect::<Vec<usize>>();
+impl Number {
+    /// Whether `(x, y)` touches one of the digits, diagonally included.
+    fn is_adjacent(&self, x: usize, y: usize) -> bool {
+        y.abs_diff(self.y) <= 1 && x + 1 >= *self.columns.start() && x <= self.columns.end() + 1
+    }
 
-    let mut gears = gears_positions
-        .iter()
-        .filter_map(|i| {
-            let (x, y) = (i % width, i / height);
-            let max_size = width * height;
-            let margin = DIRECTIONS
-                .iter()
-                .filter_map(|(dx, dy)| {
-                    let (x, y) = (x as i32, y as i32);
-                    let height = height as i32;
-                    let index = (height * (y + dy) + (x + dx)) as usize;
-                    if index > 0
-                        && index < max_size
-                        && input.get(index).map(|c| c.is_ascii_digit()) == Some(true)
-                    {
-                        Some(index)
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            if margin.len() > 1 {
-                Some(Gear {
-                    position: (x, y),
-                    possible_index: margin,
-                    numbers: Vec::new(),
-                    product: 0,
-                })
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<_>>();
+    fn contains(&self, x: usize, y: usize) -> bool {
+        y == self.y && self.columns.contains(&x)
+    }
+}
 
-    let mut is_number = false;
-    for (i, &c) in input.iter().enumerate() {
-        if i % width == 0 {
-            is_number = false;
-        }
-        match is_number {
-            true => {
-                if c.is_ascii_digit() {
-                    let number = numbers.last_mut().unwrap();
-                    number.value = number.value * 10 + (c - b'0') as u32;
-                    number.range = *number.range.start()..=i;
-                } else {
-                    is_number = false;
+/// Reads the numbers of each row, left to right.
+fn find_numbers(schematic: &Schematic) -> Vec<Number> {
+    let mut numbers = Vec::new();
+    for (y, row) in schematic.rows().enumerate() {
+        let mut current: Option<Number> = None;
+        for (x, c) in row.iter().enumerate() {
+            match (c.to_digit(10), &mut current) {
+                (Some(digit), Some(number)) => {
+                    number.value = number.value * 10 + digit;
+                    number.columns = *number.columns.start()..=x;
                 }
-            }
-            false => {
-                if c.is_ascii_digit() {
-                    numbers.push(Number {
-                        range: i..=i,
-                        value: (c - b'0') as u32,
-                        y: i / height,
-                    });
-                    is_number = true;
+                (Some(digit), None) => {
+                    current = Some(Number {
+                        value: digit,
+                        y,
+                        columns: x..=x,
+                    })
                 }
+                (None, _) => numbers.extend(current.take()),
             }
         }
+        numbers.extend(current);
     }
+    numbers
+}
 
-    let gears = gears
-        .iter_mut()
-        .filter_map(|gear| {
-            for number in numbers.iter() {
-                for points in gear.possible_index.iter() {
-                    if number.range.contains(points) {
-                        gear.numbers.push(number.clone());
-                        break;
-                    }
-                }
-            }
-            if gear.numbers.len() == 2 {
-                gear.product = gear.numbers.iter().map(|v| v.value).product::<u32>();
-                Some(gear)
-            } else {
-                None
-            }
+fn part1(schematic: &Schematic) -> u32 {
+    find_numbers(schematic)
+        .iter()
+        .filter(|number| {
+            number.columns.clone().any(|x| {
+                schematic
+                    .neighbours8(x, number.y)
+                    .any(|position| SYMBOLS.contains(schematic[position]))
+            })
+        })
+        .map(|number| number.value)
+        .sum()
+}
+
+fn part2(schematic: &Schematic, vis: &impl Visualizer) -> u32 {
+    let numbers = find_numbers(schematic);
+    let gears = schematic
+        .positions()
+        .filter(|&position| schematic[position] == '*')
+        .filter_map(|(x, y)| {
+            let numbers = numbers
+                .iter()
+                .filter(|number| number.is_adjacent(x, y))
+                .cloned()
+                .collect::<Vec<_>>();
+            (numbers.len() == 2).then_some(Gear {
+                position: (x, y),
+                numbers,
+            })
         })
         .collect::<Vec<_>>();
 
-    let result = gears.iter().map(|g| g.product).sum::<u32>();
+    let result = gears.iter().map(Gear::ratio).sum::<u32>();
 
-    if cfg!(feature = "visualize") {
+    if vis.enabled() {
         let gear = Style::new().red().bold().apply_to("*");
         let gold = Style::new().bright().yellow().bold();
         let gray = Style::new().red().bold();
-        for (i, &c) in input.iter().enumerate() {
-            if i % width == 0 {
-                print!(" ");
-                let y = i / height;
-                if y > 0 {
-                    let sum = gears
-                        .iter()
-                        .filter(|g| g.position.1 == y - 1)
-                        .map(|v| v.numbers[0].value * v.numbers[1].value)
-                        .sum::<u32>();
-                    let sum_parts: String = gears
-                        .iter()
-                        .filter(|g| g.position.1 == y - 1)
-                        .map(|g| format!("{}*{}", g.numbers[0].value, g.numbers[1].value))
-                        .join("+");
-                    if sum > 0 {
-                        print!("{} = {}", sum, sum_parts);
-                    }
+        visualize_println!(vis);
+        for (y, row) in schematic.rows().enumerate() {
+            visualize_print!(vis, " ");
+            for (x, &c) in row.iter().enumerate() {
+                if gears
+                    .iter()
+                    .any(|g| g.position == (x, y) || g.numbers.iter().any(|n| n.contains(x, y)))
+                {
+                    visualize_print!(vis, "{}", gold.apply_to(c));
+                } else if c == '*' {
+                    visualize_print!(vis, "{}", gear);
+                } else if SYMBOLS.contains(c) || c == '.' {
+                    visualize_print!(vis, " ");
+                } else if c.is_ascii_digit() {
+                    visualize_print!(vis, "{}", gray.apply_to(c));
+                } else {
+                    visualize_print!(vis, "{}", c);
                 }
-                println!();
             }
-            if gears
+            let row_gears = gears
                 .iter()
-                .any(|g| g.position.0 == i % width && g.position.1 == i / height)
-                || gears
+                .filter(|g| g.position.1 == y)
+                .collect::<Vec<_>>();
+            if !row_gears.is_empty() {
+                let sum = row_gears.iter().map(|g| g.ratio()).sum::<u32>();
+                let sum_parts = row_gears
                     .iter()
-                    .any(|g| g.numbers.iter().any(|n| n.range.contains(&i)))
-            {
-                print!("{}", gold.apply_to(*c as char));
-            } else if *c == b'*' {
-                print!("{}", gear);
-            } else if SYMBOLS.contains(*c as char) || *c == b'.' {
-                print!(" ");
-            } else if c.is_ascii_digit() {
-                print!("{}", gray.apply_to(*c as char));
-            } else {
-                print!("{}", *c as char);
+                    .map(|g| format!("{}*{}", g.numbers[0].value, g.numbers[1].value))
+                    .join("+");
+                visualize_print!(vis, " {} = {}", sum, sum_parts);
             }
+            visualize_println!(vis);
         }
-        println!();
     }
     result
 }
 
+/// A `*` next to exactly two numbers.
 #[derive(Clone, Debug)]
 struct Gear {
-    numbers: Vec<Number>,
-    product: u32,
-    #[allow(dead_code)]
     position: (usize, usize),
-    possible_index: Vec<usize>,
+    numbers: Vec<Number>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-struct Number {
-    value: u32,
-    range: RangeInclusive<usize>,
-    y: usize,
+impl Gear {
+    fn ratio(&self) -> u32 {
+        self.numbers.iter().map(|n| n.value).product()
+    }
 }
 
 pub struct Day3;
 
 impl Solution for Day3 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 3;
+    type Parsed = Schematic;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_schematic(input)?)
+    }
+
+    fn part1(schematic: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(schematic).into())
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(schematic: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(schematic, vis).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     const TEST_DATA: &str = "467..114..
 ...*......
@@ -274,18 +187,18 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_DATA), 4361);
+        assert_eq!(part1(&parse_schematic(TEST_DATA).unwrap()), 4361);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_DATA), 467835);
+        assert_eq!(part2(&parse_schematic(TEST_DATA).unwrap(), &Silent), 467835);
     }
 
     #[test]
     fn test_part2_2() {
         let input = "23.4
 ..*.";
-        assert_eq!(part2(input), 0);
+        assert_eq!(part2(&parse_schematic(input).unwrap(), &Silent), 92);
     }
 }
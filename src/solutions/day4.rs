@@ -1,47 +1,41 @@
 use console::Style;
 
-use super::Solution;
-
-fn part1(input: &str) -> u32 {
-    let start = std::time::Instant::now();
-    let mut cards = parse_cards(input);
-    for card in cards.iter_mut() {
-        if card.matches > 0 {
-            card.points = 1_u32 << (card.matches - 1);
-        }
-    }
+use crate::{visualize_print, visualize_println};
+
+use super::{
+    error::{parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+fn part1(cards: &[Card], vis: &impl Visualizer) -> u32 {
     let result = cards.iter().map(|c| c.points).sum();
-    let duration = start.elapsed();
-    println!("Part 1 took {:?}", duration);
 
     // Visualize the cards
-    if cfg!(feature = "visualize") {
+    if vis.enabled() {
         let gold = Style::new().bright().yellow().bold();
         let red = Style::new().red().bold();
         let green = Style::new().green().bold();
 
         for card in cards.iter() {
-            print!("Card {:03}: ", card.number);
+            visualize_print!(vis, "Card {:03}: ", card.number);
             for number in &card.winning_numbers {
-                print!("{:02} ", gold.apply_to(number));
+                visualize_print!(vis, "{:02} ", gold.apply_to(number));
             }
-            print!("| ");
+            visualize_print!(vis, "| ");
             for number in &card.numbers {
                 if card.winning_numbers.contains(number) {
-                    print!("{:02} ", green.apply_to(number));
+                    visualize_print!(vis, "{:02} ", green.apply_to(number));
                 } else {
-                    print!("{:02} ", red.apply_to(number));
+                    visualize_print!(vis, "{:02} ", red.apply_to(number));
                 }
             }
-            println!("=> {}", card.points);
+            visualize_println!(vis, "=> {}", card.points);
         }
     }
     result
 }
 
-fn part2(input: &str) -> u32 {
-    let start = std::time::Instant::now();
-    let cards = parse_cards(input);
+fn part2(cards: &[Card], vis: &impl Visualizer) -> u32 {
     let number_of_cards = cards.len();
     let mut card_copies = vec![1_u32; number_of_cards];
     for (i, card) in cards.iter().enumerate().filter(|(_, c)| c.matches > 0) {
@@ -57,70 +51,80 @@ fn part2(input: &str) -> u32 {
             .for_each(|c| *c += copies);
     }
     let result = card_copies.iter().sum();
-    let duration = start.elapsed();
-    println!("Part 2 took {:?}", duration);
 
     // Visualize the cards
-    if cfg!(feature = "visualize") {
+    if vis.enabled() {
         let gold = Style::new().bright().yellow().bold();
         let red = Style::new().red().bold();
         let green = Style::new().green().bold();
 
         for (i, card) in cards.iter().enumerate() {
-            print!("Card {:03}: ", card.number);
+            visualize_print!(vis, "Card {:03}: ", card.number);
             for number in &card.winning_numbers {
-                print!("{:02} ", gold.apply_to(number));
+                visualize_print!(vis, "{:02} ", gold.apply_to(number));
             }
-            print!("| ");
+            visualize_print!(vis, "| ");
             for number in &card.numbers {
                 if card.winning_numbers.contains(number) {
-                    print!("{:02} ", green.apply_to(number));
+                    visualize_print!(vis, "{:02} ", green.apply_to(number));
                 } else {
-                    print!("{:02} ", red.apply_to(number));
+                    visualize_print!(vis, "{:02} ", red.apply_to(number));
                 }
             }
-            println!("=> Copies {}", card_copies[i]);
+            visualize_println!(vis, "=> Copies {}", card_copies[i]);
         }
     }
     result
 }
 
-fn parse_cards(input: &str) -> Vec<Card> {
+fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
     input
         .lines()
         .enumerate()
-        .filter_map(|(i, line)| {
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
             let number = (i + 1) as u32;
-            let (winning_part, numbers_part) = line.split_once(" | ")?;
+            let Some((_, line)) = line.split_once(':') else {
+                return Err(ParseError::new(line, "Invalid card"));
+            };
+            let Some((winning_part, numbers_part)) = line.split_once(" | ") else {
+                return Err(ParseError::new(line, "Invalid card"));
+            };
 
             let winning_numbers = winning_part
                 .split_ascii_whitespace()
-                .filter_map(|n| n.parse().ok())
-                .collect::<Vec<u32>>();
+                .map(parse_number)
+                .collect::<Result<Vec<u32>, _>>()?;
 
             let numbers = numbers_part
                 .split_ascii_whitespace()
-                .filter_map(|n| n.parse().ok())
-                .collect::<Vec<u32>>();
+                .map(parse_number)
+                .collect::<Result<Vec<u32>, _>>()?;
 
             let matches = numbers
                 .iter()
                 .filter(|n| winning_numbers.contains(n))
                 .count();
 
-            Some(Card {
+            let points = if matches > 0 {
+                1_u32 << (matches - 1)
+            } else {
+                0
+            };
+
+            Ok(Card {
                 number,
                 winning_numbers,
                 numbers,
                 matches,
-                points: 0,
+                points,
             })
         })
         .collect()
 }
 
 #[derive(Clone, Debug)]
-struct Card {
+pub struct Card {
     number: u32,
     winning_numbers: Vec<u32>,
     numbers: Vec<u32>,
@@ -131,18 +135,26 @@ struct Card {
 pub struct Day4;
 
 impl Solution for Day4 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 4;
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_cards(input)?)
+    }
+
+    fn part1(cards: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(cards, vis).into())
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(cards: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(cards, vis).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     const TEST_DATA: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
@@ -153,11 +165,11 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_DATA), 13);
+        assert_eq!(part1(&parse_cards(TEST_DATA).unwrap(), &Silent), 13);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_DATA), 30);
+        assert_eq!(part2(&parse_cards(TEST_DATA).unwrap(), &Silent), 30);
     }
 }
@@ -1,43 +1,25 @@
 use std::{fmt::Display, ops::Range, str::Lines};
 
-use super::Solution;
-
-fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let mut seeds = Vec::new();
-    let mut seed2soil = Vec::new();
-    let mut soil2fertilizer = Vec::new();
-    let mut fertilizer2water = Vec::new();
-    let mut water2light = Vec::new();
-    let mut light2temperature = Vec::new();
-    let mut temperature2humidity = Vec::new();
-    let mut humidity2locations = Vec::new();
-    while let Some(line) = lines.next() {
-        if line.starts_with("seeds:") {
-            seeds = parse_seeds(line);
-        }
-        if line.starts_with("seed-to-soil map:") {
-            seed2soil = parse_map(&mut lines);
-        }
-        if line.starts_with("soil-to-fertilizer map:") {
-            soil2fertilizer = parse_map(&mut lines);
-        }
-        if line.starts_with("fertilizer-to-water map:") {
-            fertilizer2water = parse_map(&mut lines);
-        }
-        if line.starts_with("water-to-light map:") {
-            water2light = parse_map(&mut lines);
-        }
-        if line.starts_with("light-to-temperature map:") {
-            light2temperature = parse_map(&mut lines);
-        }
-        if line.starts_with("temperature-to-humidity map:") {
-            temperature2humidity = parse_map(&mut lines);
-        }
-        if line.starts_with("humidity-to-location map:") {
-            humidity2locations = parse_map(&mut lines);
-        }
-    }
+use crate::visualize_println;
+
+use super::{
+    common::range::intersect,
+    error::{parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
+
+fn part1(almanac: &Almanac, vis: &impl Visualizer) -> u64 {
+    let Almanac {
+        seeds,
+        seed2soil,
+        soil2fertilizer,
+        fertilizer2water,
+        water2light,
+        light2temperature,
+        temperature2humidity,
+        humidity2locations,
+    } = almanac;
+    let mut seeds = seeds.iter().copied().map(Seed::new).collect::<Vec<_>>();
 
     for seed in seeds.iter_mut() {
         if let Some(soil) = seed2soil.iter().find(|s| s.contains(seed.seed)) {
@@ -74,116 +56,87 @@ fn part1(input: &str) -> u64 {
             .map(|v| v.map(seed.humidity))
             .unwrap_or(seed.humidity);
     }
-    if cfg!(feature = "visualize") {
-        println!("Seed to soil map:");
+    if vis.enabled() {
+        visualize_println!(vis, "Seed to soil map:");
         for seed in seed2soil.iter() {
-            println!("{}", seed);
+            visualize_println!(vis, "{}", seed);
         }
-        println!();
-        println!("Soil to fertilizer map:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Soil to fertilizer map:");
         for soil in soil2fertilizer.iter() {
-            println!("{}", soil);
+            visualize_println!(vis, "{}", soil);
         }
-        println!();
-        println!("Fertilizer to water map:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Fertilizer to water map:");
         for fertilizer in fertilizer2water.iter() {
-            println!("{}", fertilizer);
+            visualize_println!(vis, "{}", fertilizer);
         }
-        println!();
-        println!("Water to light map:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Water to light map:");
         for water in water2light.iter() {
-            println!("{}", water);
+            visualize_println!(vis, "{}", water);
         }
-        println!();
-        println!("Light to temperature map:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Light to temperature map:");
         for light in light2temperature.iter() {
-            println!("{}", light);
+            visualize_println!(vis, "{}", light);
         }
-        println!();
-        println!("Temperature to humidity map:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Temperature to humidity map:");
         for temperature in temperature2humidity.iter() {
-            println!("{}", temperature);
+            visualize_println!(vis, "{}", temperature);
         }
-        println!();
-        println!("Humidity to location map:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Humidity to location map:");
         for humidity in humidity2locations.iter() {
-            println!("{}", humidity);
+            visualize_println!(vis, "{}", humidity);
         }
-        println!();
-        println!("Seeds:");
+        visualize_println!(vis);
+        visualize_println!(vis, "Seeds:");
         for seed in seeds.iter() {
-            println!("{}", seed);
+            visualize_println!(vis, "{}", seed);
         }
     }
 
     seeds.iter().map(|s| s.location).min().unwrap()
 }
 
-fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let mut seeds = Vec::new();
-    let mut seed2soil = Vec::new();
-    let mut soil2fertilizer = Vec::new();
-    let mut fertilizer2water = Vec::new();
-    let mut water2light = Vec::new();
-    let mut light2temperature = Vec::new();
-    let mut temperature2humidity = Vec::new();
-    let mut humidity2locations = Vec::new();
-    while let Some(line) = lines.next() {
-        if line.starts_with("seeds:") {
-            seeds = line
-                .split_ascii_whitespace()
-                .filter_map(|s| s.parse::<u64>().ok())
-                .collect();
-        }
-        if line.starts_with("seed-to-soil map:") {
-            seed2soil = parse_map(&mut lines);
-        }
-        if line.starts_with("soil-to-fertilizer map:") {
-            soil2fertilizer = parse_map(&mut lines);
-        }
-        if line.starts_with("fertilizer-to-water map:") {
-            fertilizer2water = parse_map(&mut lines);
-        }
-        if line.starts_with("water-to-light map:") {
-            water2light = parse_map(&mut lines);
-        }
-        if line.starts_with("light-to-temperature map:") {
-            light2temperature = parse_map(&mut lines);
-        }
-        if line.starts_with("temperature-to-humidity map:") {
-            temperature2humidity = parse_map(&mut lines);
-        }
-        if line.starts_with("humidity-to-location map:") {
-            humidity2locations = parse_map(&mut lines);
-        }
-    }
-
+fn part2(almanac: &Almanac) -> anyhow::Result<u64> {
+    let Almanac {
+        seeds,
+        seed2soil,
+        soil2fertilizer,
+        fertilizer2water,
+        water2light,
+        light2temperature,
+        temperature2humidity,
+        humidity2locations,
+    } = almanac;
     let seeds_to_locations = |seed_range: &Range<u64>| {
-        let seed_soils = source_to_target_ranges(seed_range.clone(), &seed2soil);
+        let seed_soils = source_to_target_ranges(seed_range.clone(), seed2soil);
         let soil_fertilizers = seed_soils
             .iter()
-            .flat_map(|s| source_to_target_ranges(s.start..s.end, &soil2fertilizer))
+            .flat_map(|s| source_to_target_ranges(s.start..s.end, soil2fertilizer))
             .collect::<Vec<_>>();
         let fertilizer_waters = soil_fertilizers
             .iter()
-            .flat_map(|s| source_to_target_ranges(s.start..s.end, &fertilizer2water))
+            .flat_map(|s| source_to_target_ranges(s.start..s.end, fertilizer2water))
             .collect::<Vec<_>>();
         let water_lights = fertilizer_waters
             .iter()
-            .flat_map(|s| source_to_target_ranges(s.start..s.end, &water2light))
+            .flat_map(|s| source_to_target_ranges(s.start..s.end, water2light))
             .collect::<Vec<_>>();
         let light_temperatures = water_lights
             .iter()
-            .flat_map(|s| source_to_target_ranges(s.start..s.end, &light2temperature))
+            .flat_map(|s| source_to_target_ranges(s.start..s.end, light2temperature))
             .collect::<Vec<_>>();
         let temperature_humidities = light_temperatures
             .iter()
-            .flat_map(|s| source_to_target_ranges(s.start..s.end, &temperature2humidity))
+            .flat_map(|s| source_to_target_ranges(s.start..s.end, temperature2humidity))
             .collect::<Vec<_>>();
         let humidity_locations = temperature_humidities
             .iter()
-            .flat_map(|s| source_to_target_ranges(s.start..s.end, &humidity2locations))
+            .flat_map(|s| source_to_target_ranges(s.start..s.end, humidity2locations))
             .collect::<Vec<_>>();
         humidity_locations
     };
@@ -191,20 +144,84 @@ fn part2(input: &str) -> u64 {
     let seed_ranges = seeds
         .chunks_exact(2)
         .map(|c| c[0]..(c[0] + c[1]))
+        .filter(|r| !r.is_empty())
         .collect::<Vec<_>>();
+    if seed_ranges.is_empty() {
+        anyhow::bail!("Expected the seeds in pairs of start and non-zero length");
+    }
+
+    let min_location = seed_ranges
+        .iter()
+        .flat_map(seeds_to_locations)
+        .map(|r| r.start)
+        .min();
+
+    min_location.ok_or_else(|| anyhow::anyhow!("No location for the seeds"))
+}
+
+/// The seeds to plant and the maps from each category to the next.
+pub struct Almanac {
+    seeds: Vec<u64>,
+    seed2soil: Vec<MapRange>,
+    soil2fertilizer: Vec<MapRange>,
+    fertilizer2water: Vec<MapRange>,
+    water2light: Vec<MapRange>,
+    light2temperature: Vec<MapRange>,
+    temperature2humidity: Vec<MapRange>,
+    humidity2locations: Vec<MapRange>,
+}
 
-    let mut min_location = u64::MAX;
-    for seed_range in seed_ranges.into_iter() {
-        let seeds_to_locations = seeds_to_locations(&seed_range);
-        let current_min = seeds_to_locations.into_iter().map(|r| r.start).min();
-        if let Some(current_min) = current_min {
-            if current_min < min_location {
-                min_location = current_min;
+impl Almanac {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let mut seeds = Vec::new();
+        let mut seed2soil = Vec::new();
+        let mut soil2fertilizer = Vec::new();
+        let mut fertilizer2water = Vec::new();
+        let mut water2light = Vec::new();
+        let mut light2temperature = Vec::new();
+        let mut temperature2humidity = Vec::new();
+        let mut humidity2locations = Vec::new();
+        while let Some(line) = lines.next() {
+            if line.starts_with("seeds:") {
+                seeds = parse_seeds(line)?;
+            }
+            if line.starts_with("seed-to-soil map:") {
+                seed2soil = parse_map(&mut lines)?;
+            }
+            if line.starts_with("soil-to-fertilizer map:") {
+                soil2fertilizer = parse_map(&mut lines)?;
+            }
+            if line.starts_with("fertilizer-to-water map:") {
+                fertilizer2water = parse_map(&mut lines)?;
+            }
+            if line.starts_with("water-to-light map:") {
+                water2light = parse_map(&mut lines)?;
+            }
+            if line.starts_with("light-to-temperature map:") {
+                light2temperature = parse_map(&mut lines)?;
+            }
+            if line.starts_with("temperature-to-humidity map:") {
+                temperature2humidity = parse_map(&mut lines)?;
+            }
+            if line.starts_with("humidity-to-location map:") {
+                humidity2locations = parse_map(&mut lines)?;
             }
         }
+        if seeds.is_empty() {
+            return Err(ParseError::new(&input[input.len()..], "Missing the seeds"));
+        }
+        Ok(Self {
+            seeds,
+            seed2soil,
+            soil2fertilizer,
+            fertilizer2water,
+            water2light,
+            light2temperature,
+            temperature2humidity,
+            humidity2locations,
+        })
     }
-
-    min_location
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -293,16 +310,14 @@ impl Display for MapRange {
     }
 }
 
-fn parse_seeds(input: &str) -> Vec<Seed> {
-    let (_, seeds) = input.split_once("seeds:").unwrap();
-    seeds
-        .split_whitespace()
-        .filter_map(|s| s.parse::<u64>().ok())
-        .map(Seed::new)
-        .collect()
+fn parse_seeds(input: &str) -> Result<Vec<u64>, ParseError> {
+    let Some((_, seeds)) = input.split_once("seeds:") else {
+        return Err(ParseError::new(input, "Invalid seeds"));
+    };
+    seeds.split_whitespace().map(parse_number).collect()
 }
 
-fn parse_map(lines: &mut Lines<'_>) -> Vec<MapRange> {
+fn parse_map(lines: &mut Lines<'_>) -> Result<Vec<MapRange>, ParseError> {
     let mut ranges = Vec::new();
     for line in lines.by_ref() {
         if line.is_empty() {
@@ -310,27 +325,25 @@ fn parse_map(lines: &mut Lines<'_>) -> Vec<MapRange> {
         }
         let numbers = line
             .split_whitespace()
-            .filter_map(|s| s.parse::<u64>().ok())
-            .collect::<Vec<_>>();
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()?;
         if numbers.len() != 3 {
-            eprintln!("Invalid map line: {}", line);
-            break;
+            return Err(ParseError::new(line, "Invalid map line"));
         }
         ranges.push(MapRange::new(numbers[0], numbers[1], numbers[2]));
     }
     ranges.sort_by_key(|r| r.source);
-    ranges
+    Ok(ranges)
 }
 
 fn source_to_target_ranges(range: Range<u64>, source2targets: &[MapRange]) -> Vec<Range<u64>> {
     let mut ranges = Vec::new();
     for map in source2targets.iter() {
         let source_range = map.source_range();
-        let intersect_start = source_range.start.max(range.start);
-        let intersect_end = source_range.end.min(range.end);
-        if intersect_start >= intersect_end {
+        let Some(intersection) = intersect(&source_range, &range) else {
             continue;
-        }
+        };
+        let (intersect_start, intersect_end) = (intersection.start, intersection.end);
         let target_range = map.destination_range();
         let target_start = target_range.start + (intersect_start - source_range.start);
         let target_end = target_start + (intersect_end - intersect_start);
@@ -371,18 +384,26 @@ fn join_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
 pub struct Day5;
 
 impl Solution for Day5 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 5;
+    type Parsed = Almanac;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Almanac::from_str(input)?)
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part1(almanac: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(almanac, vis).into())
+    }
+
+    fn part2(almanac: &Self::Parsed, _vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(almanac)?.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     const TEST_DATA: &str = "seeds: 79 14 55 13
 
@@ -420,11 +441,17 @@ humidity-to-location map:
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_DATA), 35);
+        assert_eq!(part1(&Almanac::from_str(TEST_DATA).unwrap(), &Silent), 35);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_DATA), 46);
+        assert_eq!(part2(&Almanac::from_str(TEST_DATA).unwrap()).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_part2_empty_ranges() {
+        let input = TEST_DATA.replace("seeds: 79 14 55 13", "seeds: 79 0 55 0");
+        assert!(part2(&Almanac::from_str(&input).unwrap()).is_err());
     }
 }
@@ -1,22 +1,47 @@
-use super::Solution;
+use crate::{visualize_print, visualize_println};
+
+use super::{
+    error::{parse_number, ParseError},
+    Answer, Solution, Visualizer,
+};
 
 // distance = holding_time * total_time - holding_time^2
 // -holding_time^2 = holding_time * total_time - distance
 // holding_time = (total_time - sqrt(total_time^2 - 4 * distance)) / 2
 // holding_time = (total_time + sqrt(total_time^2 - 4 * distance)) / 2
 
-fn part1(input: &str) -> u64 {
-    let Some((times, distances)) = input.split_once('\n') else {
-        return 0;
+/// The time and record distance of each race.
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+}
+
+impl Races {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let times = parse_line(lines.next().unwrap_or(input), "Time:")?;
+        let distances = parse_line(lines.next().unwrap_or(""), "Distance:")?;
+        Ok(Self { times, distances })
+    }
+}
+
+/// Parses the numbers of `Time: 7 15 30` after the expected `label`.
+fn parse_line(line: &str, label: &str) -> Result<Vec<u64>, ParseError> {
+    let Some(numbers) = line.trim_start().strip_prefix(label) else {
+        return Err(ParseError::new(line, format!("Expected {}", label)));
     };
-    let times = times[6..]
-        .split_ascii_whitespace()
-        .filter_map(|v| v.parse::<u64>().ok())
-        .collect::<Vec<_>>();
-    let distances = distances[10..]
-        .split_ascii_whitespace()
-        .filter_map(|v| v.parse::<u64>().ok())
-        .collect::<Vec<_>>();
+    numbers.split_ascii_whitespace().map(parse_number).collect()
+}
+
+/// Joins the digits of the values, the kerning fix of part 2: `[7, 15, 30]` is `71530`.
+fn join_digits(values: &[u64]) -> u64 {
+    values.iter().fold(0, |joined, v| {
+        joined * 10_u64.pow(v.to_string().len() as u32) + v
+    })
+}
+
+fn part1(races: &Races, vis: &impl Visualizer) -> u64 {
+    let Races { times, distances } = races;
 
     let total_permutations: u64 = times
         .iter()
@@ -26,14 +51,15 @@ fn part1(input: &str) -> u64 {
                 if cfg!(feature = "brute_force") {
                     calculate_race_brute_force(*time, *distance)
                 } else {
-                    calculate_race_optimize(*time, *distance)
+                    calculate_race_optimize(*time, *distance, vis)
                 };
-            if cfg!(feature = "visualize") {
-                println!(
-                    "Race {time}ms {distance}mm -> [{}..{}] ({})",
-                    min_holding_time, max_holding_time, permutations
-                );
-            }
+            visualize_println!(
+                vis,
+                "Race {time}ms {distance}mm -> [{}..{}] ({})",
+                min_holding_time,
+                max_holding_time,
+                permutations
+            );
             permutations
         })
         .product();
@@ -41,37 +67,25 @@ fn part1(input: &str) -> u64 {
     total_permutations
 }
 
-fn part2(input: &str) -> u64 {
-    let Some((times, distances)) = input.split_once('\n') else {
-        return 0;
-    };
-    let time = times[6..]
-        .replace(' ', "")
-        .trim()
-        .parse::<u64>()
-        .expect("Failed to parse time");
-    let distance = distances[10..]
-        .replace(' ', "")
-        .trim()
-        .parse::<u64>()
-        .expect("Failed to parse distance");
-
-    if cfg!(feature = "visualize") {
-        print!("Permutation Race {time}ms {distance}mm: ");
-    }
+fn part2(races: &Races, vis: &impl Visualizer) -> u64 {
+    let time = join_digits(&races.times);
+    let distance = join_digits(&races.distances);
+
+    visualize_print!(vis, "Permutation Race {time}ms {distance}mm: ");
 
     let (min_holding_time, max_holding_time, permutations) = if cfg!(feature = "brute_force") {
         calculate_race_brute_force(time, distance)
     } else {
-        calculate_race_optimize(time, distance)
+        calculate_race_optimize(time, distance, vis)
     };
 
-    if cfg!(feature = "visualize") {
-        println!(
-            "Race {time}ms {distance}mm -> [{}..{}] ({})",
-            min_holding_time, max_holding_time, permutations
-        );
-    }
+    visualize_println!(
+        vis,
+        "Race {time}ms {distance}mm -> [{}..{}] ({})",
+        min_holding_time,
+        max_holding_time,
+        permutations
+    );
     permutations
 }
 
@@ -129,7 +143,7 @@ fn calculate_race_brute_force(t: u64, d: u64) -> (u64, u64, u64) {
 /// Assuming that the discriminant is positive, we can calculate the two solutions
 /// and return the one that is greater than the holding time
 /// This is a lot faster than the brute force method
-fn calculate_race_optimize(t: u64, d: u64) -> (u64, u64, u64) {
+fn calculate_race_optimize(t: u64, d: u64, vis: &impl Visualizer) -> (u64, u64, u64) {
     let discriminant = (t * t) as f64 - 4.0 * d as f64;
     if discriminant < 0.0 {
         eprintln!(
@@ -174,12 +188,12 @@ fn calculate_race_optimize(t: u64, d: u64) -> (u64, u64, u64) {
             (min_holding_time..max_holding_time).count() as u64,
         )
     };
-    if cfg!(feature = "visualize") {
-        println!("Discriminant: {}", discriminant);
-        println!("Sqrt Discriminant: {}", sqrt_discriminant);
-        println!("Solution 1: {}", a1);
-        println!("Solution 2: {}", a2);
-        println!("Permutations: {}", result.2);
+    if vis.enabled() {
+        visualize_println!(vis, "Discriminant: {}", discriminant);
+        visualize_println!(vis, "Sqrt Discriminant: {}", sqrt_discriminant);
+        visualize_println!(vis, "Solution 1: {}", a1);
+        visualize_println!(vis, "Solution 2: {}", a2);
+        visualize_println!(vis, "Permutations: {}", result.2);
     }
     result
 }
@@ -187,30 +201,38 @@ fn calculate_race_optimize(t: u64, d: u64) -> (u64, u64, u64) {
 pub struct Day6;
 
 impl Solution for Day6 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 6;
+    type Parsed = Races;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Races::from_str(input)?)
+    }
+
+    fn part1(races: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(races, vis).into())
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(races: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(races, vis).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     #[test]
     fn test_part1() {
         let input = "Time:      7  15   30
 Distance:  9  40  200";
-        assert_eq!(part1(input), 288);
+        assert_eq!(part1(&Races::from_str(input).unwrap(), &Silent), 288);
     }
 
     #[test]
     fn test_part2() {
         let input = "Time:      7  15   30
 Distance:  9  40  200";
-        assert_eq!(part2(input), 71503);
+        assert_eq!(part2(&Races::from_str(input).unwrap(), &Silent), 71503);
     }
 }
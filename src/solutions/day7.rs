@@ -1,6 +1,8 @@
 use self::hand::Hand;
 
-use super::Solution;
+use crate::visualize_println;
+
+use super::{Answer, Solution, Visualizer};
 
 mod card;
 mod hand;
@@ -16,11 +18,8 @@ mod hand_type;
 /// 23432 - > Two pair
 /// A23A4 - > One pair
 /// 23456 - > High card
-fn part1(input: &str) -> u64 {
-    let mut hands = input
-        .lines()
-        .map(|line| Hand::from_str(line, false))
-        .collect::<Vec<_>>();
+fn part1(hands: &[Hand], vis: &impl Visualizer) -> u64 {
+    let mut hands = hands.to_vec();
     // Rank the hands
     hands.sort();
 
@@ -28,28 +27,29 @@ fn part1(input: &str) -> u64 {
         .iter_mut()
         .enumerate()
         .for_each(|(i, v)| v.rank = 1 + i as u64);
-    if cfg!(feature = "visualize") {
+    if vis.enabled() {
         for hand in hands.iter() {
-            println!("{}", hand);
+            visualize_println!(vis, "{}", hand);
         }
     }
     hands.iter().map(|h| h.rank * h.bid).sum()
 }
 
-fn part2(input: &str) -> u64 {
-    let mut hands = input
-        .lines()
-        .map(|line| Hand::from_str(line, true))
-        .collect::<Vec<_>>(); // Rank the hands
+fn part2(hands: &[Hand], vis: &impl Visualizer) -> u64 {
+    let mut hands = hands
+        .iter()
+        .map(|hand| hand.with_jokers(vis))
+        .collect::<Vec<_>>();
+    // Rank the hands
     hands.sort();
 
     hands
         .iter_mut()
         .enumerate()
         .for_each(|(i, v)| v.rank = 1 + i as u64);
-    if cfg!(feature = "visualize") {
+    if vis.enabled() {
         for hand in hands.iter() {
-            println!("{}", hand);
+            visualize_println!(vis, "{}", hand);
         }
     }
     hands.iter().map(|h| h.rank * h.bid).sum()
@@ -58,28 +58,46 @@ fn part2(input: &str) -> u64 {
 pub struct Day7;
 
 impl Solution for Day7 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 7;
+    /// The hands read without jokers, part 2 turns their jacks into jokers.
+    type Parsed = Vec<Hand>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let hands = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(Hand::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(hands)
+    }
+
+    fn part1(hands: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(hands, vis).into())
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(hands: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(hands, vis).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     #[test]
     fn test_part1() {
         assert_eq!(
             part1(
-                "32T3K 765
+                &Day7::parse(
+                    "32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483"
+                )
+                .unwrap(),
+                &Silent
             ),
             6440
         );
@@ -89,11 +107,15 @@ QQQJA 483"
     fn test_part2() {
         assert_eq!(
             part2(
-                "32T3K 765
+                &Day7::parse(
+                    "32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483"
+                )
+                .unwrap(),
+                &Silent
             ),
             5905
         );
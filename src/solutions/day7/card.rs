@@ -20,8 +20,8 @@ pub enum Card {
 }
 
 impl Card {
-    pub fn from_char(c: char) -> Self {
-        match c {
+    pub fn from_char(c: char) -> Option<Self> {
+        let card = match c {
             '2' => Card::Two,
             '3' => Card::Three,
             '4' => Card::Four,
@@ -35,8 +35,9 @@ impl Card {
             'Q' => Card::Queen,
             'K' => Card::King,
             'A' => Card::Ace,
-            _ => panic!("Invalid card: {}", c),
-        }
+            _ => return None,
+        };
+        Some(card)
     }
 }
 
@@ -4,8 +4,10 @@ use itertools::Itertools;
 
 use super::card::Card;
 use super::hand_type::HandType;
+use crate::solutions::error::{parse_chars, parse_number, ParseError};
+use crate::{Silent, Visualizer};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Hand {
     pub rank: u64,
     pub kind: HandType,
@@ -14,34 +16,38 @@ pub struct Hand {
 }
 
 impl Hand {
-    pub fn from_str(input: &str, replace_jokers: bool) -> Self {
+    /// Parses `32T3K 765`, the cards followed by the bid, without visualizing how its type
+    /// is found.
+    pub fn from_str(input: &str) -> Result<Self, ParseError> {
         let Some((cards_str, bid)) = input.split_once(' ') else {
-            panic!("Invalid input: {}", input);
+            return Err(ParseError::new(input, "Invalid hand"));
         };
-        let cards: [Card; 5] = cards_str
-            .chars()
-            .map(Card::from_char)
-            .collect::<Vec<_>>()
+        let cards = parse_chars(cards_str, "Invalid card", Card::from_char)
+            .collect::<Result<Vec<_>, _>>()?;
+        let cards: [Card; 5] = cards
             .try_into()
-            .expect("Must be 5 cards");
-        let bid = bid.parse::<u64>().unwrap();
-        let cards = if replace_jokers {
-            let mut cards = cards;
-            for card in cards.iter_mut() {
-                if *card == Card::Jack {
-                    *card = Card::Joker;
-                }
-            }
-            cards
-        } else {
-            cards
-        };
-        let kind = HandType::from_cards(&cards);
-        Self {
+            .map_err(|_| ParseError::new(cards_str, "Expected 5 cards"))?;
+        let bid = parse_number(bid.trim())?;
+        Ok(Self {
             rank: 0,
-            kind,
+            kind: HandType::from_cards(&cards, &Silent),
             cards,
             bid,
+        })
+    }
+
+    /// The same hand with its jacks read as jokers.
+    pub fn with_jokers(&self, vis: &impl Visualizer) -> Self {
+        let mut cards = self.cards;
+        for card in cards.iter_mut() {
+            if *card == Card::Jack {
+                *card = Card::Joker;
+            }
+        }
+        Self {
+            kind: HandType::from_cards(&cards, vis),
+            cards,
+            ..*self
         }
     }
 }
@@ -4,7 +4,7 @@ use std::{
     fmt::Display,
 };
 
-use crate::{visualize_print, visualize_println};
+use crate::{visualize_print, visualize_println, Visualizer};
 
 use super::card::Card;
 
@@ -32,7 +32,7 @@ impl HandType {
         }
     }
 
-    pub fn from_cards(cards: &[Card; 5]) -> Self {
+    pub fn from_cards(cards: &[Card; 5], vis: &impl Visualizer) -> Self {
         let mut cards = *cards;
         // Sort the cards ascending
         cards.sort();
@@ -46,6 +46,7 @@ impl HandType {
         }
 
         visualize_print!(
+            vis,
             "{} -> {:?} ({}) {:?}",
             cards
                 .iter()
@@ -58,12 +59,12 @@ impl HandType {
         );
 
         if unique_cards_len <= 1 {
-            visualize_println!(" Five of a Kind");
+            visualize_println!(vis, " Five of a Kind");
             return HandType::FiveOfAKind(cards[0]);
         }
 
         if jokers > 0 {
-            return Self::handle_jokers(jokers, &cards, unique_cards, counts);
+            return Self::handle_jokers(jokers, &cards, unique_cards, counts, vis);
         }
 
         // Four of a Kind
@@ -72,12 +73,13 @@ impl HandType {
             let three_of_a_kind = counts.iter().find(|(_, v)| **v == 3).map(|v| *v.0);
             let two_of_a_kind = counts.iter().find(|(_, v)| **v == 2).map(|v| *v.0);
             if let Some(four_of_a_kind) = four_of_a_kind {
-                visualize_println!(" Four of a Kind {}", four_of_a_kind);
+                visualize_println!(vis, " Four of a Kind {}", four_of_a_kind);
                 return HandType::FourOfAKind(four_of_a_kind);
             } else if let (Some(three_of_a_kind), Some(two_of_a_kind)) =
                 (three_of_a_kind, two_of_a_kind)
             {
                 visualize_println!(
+                    vis,
                     " Full House with {}(3) and {}(2)",
                     three_of_a_kind,
                     two_of_a_kind
@@ -98,12 +100,13 @@ impl HandType {
                 .collect::<Vec<_>>();
             // AAA21
             if let Some(three_of_a_kind) = three_of_a_kind {
-                visualize_println!(" Three of a Kind {}", three_of_a_kind);
+                visualize_println!(vis, " Three of a Kind {}", three_of_a_kind);
                 return HandType::ThreeOfAKind(three_of_a_kind);
             }
             // AA221
             if two_of_a_kind.len() == 2 {
                 visualize_println!(
+                    vis,
                     " Two Pairs with {} and {}",
                     two_of_a_kind[0],
                     two_of_a_kind[1]
@@ -117,12 +120,12 @@ impl HandType {
         // A pair AA234
         if unique_cards_len == 4 {
             let two_of_a_kind = counts.iter().find(|(_, v)| **v == 2).map(|v| *v.0).unwrap();
-            visualize_println!(" One pair {}", two_of_a_kind);
+            visualize_println!(vis, " One pair {}", two_of_a_kind);
             return HandType::OnePair(two_of_a_kind);
         }
 
         // Five of a Kind where the fifth card is the highest card due to sort ascending
-        visualize_println!(" High card {}", cards[4]);
+        visualize_println!(vis, " High card {}", cards[4]);
         HandType::HighCard(cards[4])
     }
 
@@ -132,6 +135,7 @@ impl HandType {
         cards: &[Card; 5],
         mut unique_cards: HashSet<Card>,
         mut counts: HashMap<Card, u8>,
+        vis: &impl Visualizer,
     ) -> Self {
         let no_jokers = &cards[jokers..];
 
@@ -141,12 +145,12 @@ impl HandType {
         let unique_cards_len = unique_cards.len();
         let last_card = no_jokers.last().cloned().unwrap();
 
-        visualize_print!(" No jokers: {:?}", no_jokers);
-        visualize_print!(" Counts: {:?}", counts);
+        visualize_print!(vis, " No jokers: {:?}", no_jokers);
+        visualize_print!(vis, " Counts: {:?}", counts);
 
         // AAAAJ or AAAJJ or AAJJJ or AJJJJ
         if unique_cards_len <= 1 {
-            visualize_println!(" Five of a Kind");
+            visualize_println!(vis, " Five of a Kind");
             return HandType::FiveOfAKind(no_jokers[0]);
         }
 
@@ -156,7 +160,7 @@ impl HandType {
                 .windows(4 - jokers)
                 .any(|cards| cards.iter().dedup().count() == 1)
         {
-            visualize_println!(" Four of a Kind");
+            visualize_println!(vis, " Four of a Kind");
             let highest_counted_card = counts
                 .iter()
                 .max_by_key(|(_, v)| **v)
@@ -168,23 +172,23 @@ impl HandType {
         if jokers == 1 {
             if unique_cards_len == 2 {
                 // AAA8J
-                visualize_println!(" Full House");
+                visualize_println!(vis, " Full House");
                 return HandType::FullHouse(last_card, no_jokers[0]);
             } else if unique_cards_len == 3 {
                 // 2344J
-                visualize_println!(" Three of a Kind");
+                visualize_println!(vis, " Three of a Kind");
                 let higher_card = counts.iter().find(|(_, v)| **v == 2).map(|v| *v.0).unwrap();
                 return HandType::ThreeOfAKind(higher_card);
             } else if unique_cards_len == 4 {
                 // ATJ29
-                visualize_println!(" One Pair {}", last_card);
+                visualize_println!(vis, " One Pair {}", last_card);
                 return HandType::OnePair(last_card);
             }
         }
 
         // 234JJ
         if jokers == 2 && unique_cards_len == 3 {
-            visualize_println!(" Three of a Kind");
+            visualize_println!(vis, " Three of a Kind");
             return HandType::ThreeOfAKind(last_card);
         }
         // This should not happen
@@ -2,16 +2,25 @@ use crate::visualize_println;
 
 use self::{
     direction::Direction,
-    node::{encode_string, Node},
+    node::{decode_string, encode_string, Node},
 };
 
-use super::Solution;
+use super::{common::math::lcm, error::ParseError, Answer, Solution, Visualizer};
 
 mod direction;
 mod node;
 
-fn part1(input: &str) -> u64 {
-    let (directions, _, nodes) = parse_input(input);
+/// The left/right instructions and the nodes, linked by index.
+pub struct Network {
+    directions: Vec<Direction>,
+    positions: Vec<u32>,
+    nodes: Vec<Node>,
+}
+
+fn part1(network: &Network, vis: &impl Visualizer) -> anyhow::Result<u64> {
+    let Network {
+        directions, nodes, ..
+    } = network;
 
     let start = "AAA";
     let goal = "ZZZ";
@@ -19,11 +28,15 @@ fn part1(input: &str) -> u64 {
     let goal = encode_string(goal);
 
     // find_path_steps(start, goal, &directions, &nodes, &nodes_refs)
-    step_all(start, goal, &directions, &nodes)
+    step_all(start, goal, directions, nodes, vis)
 }
 
-fn part2(input: &str) -> u64 {
-    let (directions, positions, nodes) = parse_input(input);
+fn part2(network: &Network, vis: &impl Visualizer) -> anyhow::Result<u64> {
+    let Network {
+        directions,
+        positions,
+        nodes,
+    } = network;
     let start = "A";
     let goal = "Z";
     let start = encode_string(start);
@@ -41,33 +54,40 @@ fn part2(input: &str) -> u64 {
     // luck, but it works.
     next_nodes
         .iter()
-        .map(|n| step_all(positions[n.index], goal, &directions, &nodes))
-        .fold(1, lcm)
+        .map(|n| step_all(positions[n.index], goal, directions, nodes, vis))
+        .try_fold(1, |a, b| Ok(lcm(a, b?)))
 }
 
-fn parse_input(input: &str) -> (Vec<Direction>, Vec<u32>, Vec<Node>) {
-    let _start_time = std::time::Instant::now();
+fn parse_input(input: &str) -> Result<Network, ParseError> {
     let mut lines = input.lines().filter(|line| !line.is_empty());
-    let directions = Direction::from_line(lines.next().unwrap());
-    let mut nodes = lines.map(Node::from_line).collect::<Vec<_>>();
+    let first = lines.next().unwrap_or(input);
+    let directions = Direction::from_line(first)?;
+    if directions.is_empty() {
+        return Err(ParseError::new(first, "Missing the directions"));
+    }
+    let lines = lines.collect::<Vec<_>>();
+    let mut nodes = lines
+        .iter()
+        .map(|line| Node::from_line(line))
+        .collect::<Result<Vec<_>, _>>()?;
     let positions = nodes.iter().map(|n| n.name).collect::<Vec<_>>();
 
-    for (index, node) in nodes.iter_mut().enumerate() {
+    for ((index, node), line) in nodes.iter_mut().enumerate().zip(lines) {
+        let find = |name| {
+            positions
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| ParseError::new(line, "Links to an unknown node"))
+        };
         node.node_ref.index = index;
-        node.node_ref.left = positions
-            .iter()
-            .position(|name| name == &node.left_string)
-            .unwrap();
-        node.node_ref.right = positions
-            .iter()
-            .position(|name| name == &node.right_string)
-            .unwrap();
+        node.node_ref.left = find(&node.left_string)?;
+        node.node_ref.right = find(&node.right_string)?;
     }
-    visualize_println!(
-        "Time parse and build linked list: {:?}",
-        _start_time.elapsed()
-    );
-    (directions, positions, nodes)
+    Ok(Network {
+        directions,
+        positions,
+        nodes,
+    })
 }
 
 /// Finds the shortest path to the goal.
@@ -86,8 +106,14 @@ fn parse_input(input: &str) -> (Vec<Direction>, Vec<u32>, Vec<Node>) {
 ///
 /// # Returns
 ///
-/// The number of steps to reach the goal.
-fn step_all(start: u32, goal: u32, directions: &[Direction], nodes: &[Node]) -> u64 {
+/// The number of steps to reach the goal, or an error when there is no start or no goal.
+fn step_all(
+    start: u32,
+    goal: u32,
+    directions: &[Direction],
+    nodes: &[Node],
+    vis: &impl Visualizer,
+) -> anyhow::Result<u64> {
     let goals_index = nodes
         .iter()
         .filter(|n| n.match_name(goal))
@@ -99,8 +125,16 @@ fn step_all(start: u32, goal: u32, directions: &[Direction], nodes: &[Node]) ->
         .filter(|n| n.match_name(start))
         .map(|n| &n.node_ref)
         .collect::<Vec<_>>();
+    if next_nodes.is_empty() || goals_index.is_empty() {
+        anyhow::bail!(
+            "Missing the {} or {} nodes",
+            decode_string(start),
+            decode_string(goal)
+        );
+    }
 
     visualize_println!(
+        vis,
         "Start: {:?}",
         next_nodes
             .iter()
@@ -108,6 +142,7 @@ fn step_all(start: u32, goal: u32, directions: &[Direction], nodes: &[Node]) ->
             .collect::<Vec<_>>()
     );
     visualize_println!(
+        vis,
         "Possible Goals: {:?}",
         nodes
             .iter()
@@ -133,7 +168,7 @@ fn step_all(start: u32, goal: u32, directions: &[Direction], nodes: &[Node]) ->
             };
         });
 
-        if cfg!(feature = "visualize") {
+        if vis.enabled() {
             let starts = _current_index
                 .iter()
                 .map(|i| nodes[*i].name())
@@ -164,7 +199,8 @@ fn step_all(start: u32, goal: u32, directions: &[Direction], nodes: &[Node]) ->
                 .count()
                 >= 3
             {
-                println!(
+                visualize_println!(
+                    vis,
                     "Step:{:09} {:02} | Direction:{} | Nodes:{}",
                     steps,
                     count_goals,
@@ -181,70 +217,49 @@ fn step_all(start: u32, goal: u32, directions: &[Direction], nodes: &[Node]) ->
         steps += 1;
         direction_index += 1;
     }
-    visualize_println!("Steps: {}", steps);
-    steps
-}
-
-/// Calculates the least common multiple (LCM) of two numbers.
-///
-/// # Arguments
-///
-/// * `a` - The first number.
-/// * `b` - The second number.
-///
-/// # Returns
-///
-/// The least common multiple of `a` and `b`.
-#[inline(always)]
-fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
-}
-
-/// Calculates the greatest common divisor (GCD) of two numbers.
-///
-/// # Arguments
-///
-/// * `a` - The first number.
-/// * `b` - The second number.
-///
-/// # Returns
-///
-/// The GCD of `a` and `b`.
-#[inline(always)]
-fn gcd(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
+    visualize_println!(vis, "Steps: {}", steps);
+    Ok(steps)
 }
 
 pub struct Day8;
 
 impl Solution for Day8 {
-    fn solve_part1(input: &str) -> anyhow::Result<String> {
-        Ok(part1(input).to_string())
+    const DAY: u32 = 8;
+    type Parsed = Network;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(network: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(network, vis)?.into())
     }
 
-    fn solve_part2(input: &str) -> anyhow::Result<String> {
-        Ok(part2(input).to_string())
+    fn part2(network: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(network, vis)?.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     #[test]
     fn test_part1() {
         assert_eq!(
             part1(
-                "LLR
+                &parse_input(
+                    "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)"
-            ),
+                )
+                .unwrap(),
+                &Silent
+            )
+            .unwrap(),
             6
         );
     }
@@ -253,7 +268,8 @@ ZZZ = (ZZZ, ZZZ)"
     fn test_part2() {
         assert_eq!(
             part2(
-                "LR
+                &parse_input(
+                    "LR
 
 11A = (11B, XXX)
 11B = (XXX, 11Z)
@@ -263,8 +279,19 @@ ZZZ = (ZZZ, ZZZ)"
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)"
-            ),
+                )
+                .unwrap(),
+                &Silent
+            )
+            .unwrap(),
             6
         );
     }
+
+    #[test]
+    fn test_missing_nodes() {
+        let network = parse_input("LR\n\nAAA = (AAA, AAA)").unwrap();
+        assert!(part1(&network, &Silent).is_err());
+        assert!(parse_input("").is_err());
+    }
 }
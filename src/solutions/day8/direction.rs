@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter};
 
+use crate::solutions::error::{parse_chars, ParseError};
+
 #[derive(Debug)]
 pub enum Direction {
     Left,
@@ -7,18 +9,13 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn from_line(line: &str) -> Vec<Self> {
-        line.chars().map(|c| c.into()).collect()
-    }
-}
-
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
-        match value {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Invalid direction: {}", value),
-        }
+    pub fn from_line(line: &str) -> Result<Vec<Self>, ParseError> {
+        parse_chars(line, "Invalid direction", |c| match c {
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            _ => None,
+        })
+        .collect()
     }
 }
 
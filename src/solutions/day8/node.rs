@@ -1,3 +1,5 @@
+use crate::solutions::error::ParseError;
+
 #[derive(Debug)]
 pub struct Node {
     pub name: u32,
@@ -14,24 +16,29 @@ pub struct NodeRef {
 }
 
 impl Node {
-    pub fn from_line(input: &str) -> Self {
-        let mut parts = input.split(" = ");
-        let name = encode_string(parts.next().unwrap());
-        let to_parse = parts.next().unwrap();
-        let (left_string, right_string) = to_parse.split_once(", ").expect("Invalid input");
-        let left_string = encode_string(&left_string[1..]);
-        let right_string = encode_string(&right_string[..right_string.len() - 1]);
-
-        Self {
-            name,
-            left_string,
-            right_string,
+    /// Parses `AAA = (BBB, CCC)`.
+    pub fn from_line(input: &str) -> Result<Self, ParseError> {
+        let Some((name, to_parse)) = input.split_once(" = ") else {
+            return Err(ParseError::new(input, "Invalid node"));
+        };
+        let Some((left_string, right_string)) = to_parse
+            .strip_prefix('(')
+            .and_then(|to_parse| to_parse.strip_suffix(')'))
+            .and_then(|to_parse| to_parse.split_once(", "))
+        else {
+            return Err(ParseError::new(to_parse, "Invalid node links"));
+        };
+
+        Ok(Self {
+            name: parse_name(name)?,
+            left_string: parse_name(left_string)?,
+            right_string: parse_name(right_string)?,
             node_ref: NodeRef {
                 index: 0,
                 left: 0,
                 right: 0,
             },
-        }
+        })
     }
 
     pub fn name(&self) -> String {
@@ -43,6 +50,19 @@ impl Node {
     }
 }
 
+/// Checks the name can be encoded, up to 3 uppercase letters or digits.
+fn parse_name(input: &str) -> Result<u32, ParseError> {
+    let name = input.trim();
+    let valid = (1..=3).contains(&name.len())
+        && name
+            .bytes()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    if !valid {
+        return Err(ParseError::new(name, "Invalid node name"));
+    }
+    Ok(encode_string(name))
+}
+
 /// Encode string as a number
 ///
 /// "ABC" -> 010203
@@ -81,7 +101,7 @@ pub fn encode_string(input: &str) -> u32 {
 /// 0x010203 -> "ABC"
 /// 0x010101 -> "AAA"
 /// 0xFFFF02 -> "B"
-fn decode_string(input: u32) -> String {
+pub fn decode_string(input: u32) -> String {
     let mut decoded_chars = Vec::new();
     let mut raw_input = input;
 
@@ -1,124 +1,23 @@
-use std::{
-    ops::{Deref, DerefMut},
-    vec,
-};
+use super::{common::history::ValueHistory, error::ParseError, Answer, Solution, Visualizer};
 
-use super::Solution;
-
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-struct Values(Vec<i64>);
-
-impl Deref for Values {
-    type Target = Vec<i64>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl DerefMut for Values {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-struct ValueHistory {
-    steps: Vec<Values>,
-    firsts: Vec<i64>,
-    lasts: Vec<i64>,
-    first: i64,
-    last: i64,
-}
-
-impl ValueHistory {
-    fn from_str(input: &str) -> Self {
-        let base = Values(input.split(' ').filter_map(|s| s.parse().ok()).collect());
-        let first = *base.first().unwrap();
-        let last = *base.last().unwrap();
-        Self {
-            steps: vec![base],
-            firsts: vec![first],
-            lasts: vec![last],
-            first: 0,
-            last: 0,
-        }
-    }
-
-    fn diff(&mut self) {
-        fn inner_diff(values: &[i64]) -> Values {
-            let mut result = Vec::new();
-            for i in 0..values.len() - 1 {
-                result.push(values[i + 1] - values[i]);
-            }
-            Values(result)
-        }
-
-        let mut base = self.steps.last().unwrap().clone();
-        while base.iter().any(|v| *v != 0) {
-            let diff = inner_diff(&base);
-            #[cfg(feature = "visualize")]
-            {
-                self.steps.push(diff.clone());
-            }
-            self.lasts.push(*diff.last().unwrap());
-            self.firsts.push(*diff.first().unwrap());
-            base = diff;
-        }
-
-        #[cfg(feature = "visualize")]
-        {
-            for (i, s) in self.steps.iter().enumerate() {
-                visualize_println!("step {}: {:?}", i, s);
-            }
-        }
-
-        #[cfg(feature = "visualize")]
-        if let Some(s) = self.steps.last_mut() {
-            s.push(0);
-            s.insert(0, 0);
-        }
-        let mut current_last = 0;
-        let mut current_first = 0;
-        for i in (1..self.lasts.len()).rev() {
-            let previous_last = self.lasts[i - 1];
-            let previous_first = self.firsts[i - 1];
-            current_last += previous_last;
-            current_first = previous_first - current_first;
-            #[cfg(feature = "visualize")]
-            {
-                self.steps[i - 1].push(current_last);
-                self.steps[i - 1].insert(0, current_first);
-            }
-        }
-        self.last = current_last;
-        self.first = current_first;
-
-        #[cfg(feature = "visualize")]
-        {
-            for (i, s) in self.steps.iter().enumerate() {
-                visualize_println!("step {}: {:?}", i, s);
-            }
-        }
-    }
-}
-
-fn part1(input: &str) -> i64 {
-    let mut history = input
+fn parse_histories(input: &str) -> Result<Vec<ValueHistory>, ParseError> {
+    input
         .lines()
+        .filter(|line| !line.is_empty())
         .map(ValueHistory::from_str)
-        .collect::<Vec<_>>();
-    history.iter_mut().for_each(|h| h.diff());
+        .collect()
+}
+
+fn part1(histories: &[ValueHistory], vis: &impl Visualizer) -> i64 {
+    let mut history = histories.to_vec();
+    history.iter_mut().for_each(|h| h.diff(vis));
     let result = history.iter().map(|v| v.last).sum::<i64>();
     result
 }
 
-fn part2(input: &str) -> i64 {
-    let mut history = input
-        .lines()
-        .map(ValueHistory::from_str)
-        .collect::<Vec<_>>();
-    history.iter_mut().for_each(|h| h.diff());
+fn part2(histories: &[ValueHistory], vis: &impl Visualizer) -> i64 {
+    let mut history = histories.to_vec();
+    history.iter_mut().for_each(|h| h.diff(vis));
     let result = history.iter().map(|v| v.first).sum::<i64>();
     result
 }
@@ -126,26 +25,38 @@ fn part2(input: &str) -> i64 {
 pub struct Day9;
 
 impl Solution for Day9 {
-    fn solve_part1(_input: &str) -> anyhow::Result<String> {
-        Ok(part1(_input).to_string())
+    const DAY: u32 = 9;
+    type Parsed = Vec<ValueHistory>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_histories(input)?)
     }
 
-    fn solve_part2(_input: &str) -> anyhow::Result<String> {
-        Ok(part2(_input).to_string())
+    fn part1(histories: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part1(histories, vis).into())
+    }
+
+    fn part2(histories: &Self::Parsed, vis: &impl Visualizer) -> anyhow::Result<Answer> {
+        Ok(part2(histories, vis).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Silent;
 
     #[test]
     fn test_part1() {
         assert_eq!(
             part1(
-                "0 3 6 9 12 15
+                &parse_histories(
+                    "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45"
+                )
+                .unwrap(),
+                &Silent
             ),
             114
         );
@@ -155,9 +66,13 @@ mod tests {
     fn test_part2() {
         assert_eq!(
             part2(
-                "0 3 6 9 12 15
+                &parse_histories(
+                    "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45"
+                )
+                .unwrap(),
+                &Silent
             ),
             2
         );
@@ -165,6 +80,9 @@ mod tests {
 
     #[test]
     fn test_part2_1() {
-        assert_eq!(part2("10  13  16  21  30  45"), 5);
+        assert_eq!(
+            part2(&parse_histories("10  13  16  21  30  45").unwrap(), &Silent),
+            5
+        );
     }
 }
@@ -0,0 +1,122 @@
+use std::{fmt, str::FromStr};
+
+/// Malformed puzzle input, pointing at the offending text.
+///
+/// Parsers only see part of the input, so they create the error with [`ParseError::new`]
+/// from the slice they could not read, and [`ParseError::locate`] later finds that slice
+/// back in the whole input to fill in the day, line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    /// The line of the offending text, starting at 1, 0 until located.
+    pub line: usize,
+    /// The column of the offending text in characters, starting at 1, 0 until located.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+    /// Where `text` starts in memory, to find it back in the input.
+    address: usize,
+}
+
+impl ParseError {
+    /// An error about `text`, which must be a slice of the input for it to be located.
+    pub fn new(text: &str, message: impl Into<String>) -> Self {
+        Self {
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            message: message.into(),
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    /// Sets the day and finds the line and column of the text in `input`.
+    ///
+    /// The location is left unknown when the text is not a slice of `input`.
+    pub fn locate(mut self, day: u32, input: &str) -> Self {
+        self.day = day;
+        let Some(offset) = self
+            .address
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len() && input.is_char_boundary(offset))
+        else {
+            return self;
+        };
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.line = before.matches('\n').count() + 1;
+        self.column = before[line_start..].chars().count() + 1;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.day > 0 {
+            write!(f, "Day {}", self.day)?;
+            if self.line > 0 {
+                write!(f, ", line {}, column {}", self.line, self.column)?;
+            }
+            write!(f, ": ")?;
+        }
+        write!(f, "{}: {:?}", self.message, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a number, the usual source of parse errors.
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(text, "Invalid number"))
+}
+
+/// Parses exactly `N` numbers separated by `separator`, like the `1,0,1` of a position.
+pub fn parse_numbers<T: FromStr, const N: usize>(
+    text: &str,
+    separator: char,
+) -> Result<[T; N], ParseError> {
+    let values = text
+        .split(separator)
+        .map(|v| parse_number(v.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    values
+        .try_into()
+        .map_err(|_| ParseError::new(text, format!("Expected {} numbers", N)))
+}
+
+/// Parses each character of `line` with `parse`, the error pointing at the first one
+/// it rejects.
+pub fn parse_chars<'a, T>(
+    line: &'a str,
+    message: &'a str,
+    mut parse: impl FnMut(char) -> Option<T> + 'a,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    line.char_indices().map(move |(i, c)| {
+        parse(c).ok_or_else(|| ParseError::new(&line[i..i + c.len_utf8()], message))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "32T3K 765\nT55X5 684";
+        let error = ParseError::new(&input[13..14], "Invalid card").locate(7, input);
+        assert_eq!((error.day, error.line, error.column), (7, 2, 4));
+        assert_eq!(
+            error.to_string(),
+            "Day 7, line 2, column 4: Invalid card: \"X\""
+        );
+    }
+
+    #[test]
+    fn test_not_located() {
+        let error = ParseError::new("X", "Invalid card").locate(7, "32T3K 765");
+        assert_eq!((error.line, error.column), (0, 0));
+        assert_eq!(error.to_string(), "Day 7: Invalid card: \"X\"");
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fmt::{self, Write},
+    sync::Mutex,
+};
+
+use console::Style;
+
+use super::Animation;
+
+/// Where the solutions show how they get to their answers.
+///
+/// The parts are generic over it, so [`Silent`] makes the compiler drop the visualization
+/// code entirely, as the benchmarks need, while [`Printer`] shows it on the terminal. Use
+/// [`visualize_print!`](crate::visualize_print) and
+/// [`visualize_println!`](crate::visualize_println) to write to it, or check
+/// [`Visualizer::enabled`] before building anything expensive to show.
+pub trait Visualizer: Sync {
+    /// Whether anything shown is kept, known at compile time.
+    const ENABLED: bool;
+
+    fn print(&self, args: fmt::Arguments<'_>);
+
+    /// Shows an animation the part recorded while running.
+    fn animate(&self, animation: Animation);
+
+    fn enabled(&self) -> bool {
+        Self::ENABLED
+    }
+}
+
+/// Discards everything, the default when not asked to visualize.
+pub struct Silent;
+
+impl Visualizer for Silent {
+    const ENABLED: bool = false;
+
+    fn print(&self, _args: fmt::Arguments<'_>) {}
+
+    fn animate(&self, _animation: Animation) {}
+}
+
+/// Prints everything to the standard output.
+///
+/// A terminal cannot play animations back, so only their last frame is printed.
+pub struct Printer;
+
+impl Visualizer for Printer {
+    const ENABLED: bool = true;
+
+    fn print(&self, args: fmt::Arguments<'_>) {
+        print!("{}", args);
+    }
+
+    fn animate(&self, animation: Animation) {
+        let Some(last) = animation.len().checked_sub(1) else {
+            return;
+        };
+        let step = animation.step(last);
+        let highlight = Style::new().bright().yellow().bold();
+        println!(
+            "{} ({} frames, open the tui to play them)",
+            animation.title,
+            animation.len()
+        );
+        for (y, row) in animation.grid_at(last).iter().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                if step.highlights.contains(&(x, y)) {
+                    print!("{}", highlight.apply_to(c));
+                } else {
+                    print!("{}", c);
+                }
+            }
+            println!();
+        }
+        println!("{}", step.caption);
+    }
+}
+
+/// Keeps everything in memory, for the TUI to show it once the parts are done.
+#[derive(Default)]
+pub struct Recorder {
+    text: Mutex<String>,
+    animations: Mutex<Vec<Animation>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything recorded so far, leaving the recorder empty.
+    pub fn take(&self) -> String {
+        std::mem::take(&mut *self.text.lock().unwrap())
+    }
+
+    /// The animations recorded so far, in order, leaving none.
+    pub fn take_animations(&self) -> Vec<Animation> {
+        std::mem::take(&mut *self.animations.lock().unwrap())
+    }
+}
+
+impl Visualizer for Recorder {
+    const ENABLED: bool = true;
+
+    fn print(&self, args: fmt::Arguments<'_>) {
+        // Writing to a `String` cannot fail.
+        let _ = self.text.lock().unwrap().write_fmt(args);
+    }
+
+    fn animate(&self, animation: Animation) {
+        self.animations.lock().unwrap().push(animation);
+    }
+}
+
+/// Where [`DaySolution::run`](crate::DaySolution::run) sends the visualization.
+#[derive(Clone, Copy)]
+pub enum Visualization<'a> {
+    /// Nothing is visualized, the parts run as fast as in the benchmarks.
+    Off,
+    Print,
+    Record(&'a Recorder),
+}
+
+/// Like [`print!`], to the given [`Visualizer`].
+#[macro_export]
+macro_rules! visualize_print {
+    ($vis:expr, $($arg:tt)*) => {
+        if $crate::Visualizer::enabled($vis) {
+            $crate::Visualizer::print($vis, format_args!($($arg)*));
+        }
+    };
+}
+
+/// Like [`println!`], to the given [`Visualizer`].
+#[macro_export]
+macro_rules! visualize_println {
+    ($vis:expr) => {
+        $crate::visualize_print!($vis, "\n")
+    };
+    ($vis:expr, $($arg:tt)*) => {
+        if $crate::Visualizer::enabled($vis) {
+            $crate::Visualizer::print($vis, format_args!($($arg)*));
+            $crate::Visualizer::print($vis, format_args!("\n"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder() {
+        let recorder = Recorder::new();
+        crate::visualize_print!(&recorder, "{} + {}", 1, 2);
+        crate::visualize_println!(&recorder, " = {}", 3);
+        crate::visualize_println!(&recorder);
+        assert_eq!(recorder.take(), "1 + 2 = 3\n\n");
+        assert_eq!(recorder.take(), "");
+
+        recorder.animate(Animation::new("Walk", ["."]));
+        assert_eq!(recorder.take_animations().len(), 1);
+        assert!(recorder.take_animations().is_empty());
+    }
+}
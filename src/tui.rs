@@ -0,0 +1,455 @@
+use std::{
+    io::{stdout, Stdout},
+    time::Duration,
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    ExecutableCommand,
+};
+use ratatui::{
+    prelude::{Constraint, CrosstermBackend, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+
+use aoc2023::{read_data, Animation, DaySolution, Recorder, Visualization, SOLUTIONS};
+
+use crate::{report::Timing, run_day};
+
+const HELP: &str = "↑/↓ day  1/2/b parts  Enter run  PgUp/PgDn scroll  \
+                    Space play/pause  ←/→ frame  +/- speed  r restart  q quit";
+
+/// How many lines PgUp and PgDn scroll the visualization by.
+const PAGE: u16 = 20;
+
+/// How often a playing animation moves forward.
+const TICK: Duration = Duration::from_millis(50);
+
+/// The most frames an animation can move forward per tick.
+const MAX_SPEED: usize = 1024;
+
+/// Runs the explorer until the user quits, restoring the terminal even on errors.
+pub fn run() -> anyhow::Result<()> {
+    restore_on_panic();
+    enable_raw_mode()?;
+    let screen = stdout().execute(EnterAlternateScreen).map(|_| ());
+    let result = screen.and_then(|()| {
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        App::new().event_loop(&mut terminal)
+    });
+    restore_terminal()?;
+    Ok(result?)
+}
+
+fn restore_terminal() -> std::io::Result<()> {
+    stdout().execute(LeaveAlternateScreen)?;
+    disable_raw_mode()
+}
+
+/// Restores the terminal before a panic is reported, like one in a day run with Enter.
+///
+/// The panic unwinds past `run` without going back through it, so without this the message
+/// would be printed on the alternate screen and the shell left in raw mode.
+fn restore_on_panic() {
+    let report = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Nothing better to do if it fails, the panic still has to be reported.
+        let _ = restore_terminal();
+        report(info);
+    }));
+}
+
+/// The state of the explorer, everything drawn comes from here.
+struct App {
+    days: ListState,
+    parts: Vec<u32>,
+    /// The parse time and answer of each part of the last run, or why it failed.
+    report: Vec<String>,
+    /// What the last run visualized, without its colors.
+    output: String,
+    scroll: u16,
+    player: Player,
+}
+
+impl App {
+    fn new() -> Self {
+        Self {
+            days: ListState::default().with_selected(Some(0)),
+            parts: vec![1, 2],
+            report: Vec::new(),
+            output: String::new(),
+            scroll: 0,
+            player: Player::new(Vec::new()),
+        }
+    }
+
+    fn event_loop(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if self.player.playing && !event::poll(TICK)? {
+                self.player.forward(self.player.speed);
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if key.code == KeyCode::Enter {
+                self.report = vec![format!("Running day {}...", self.solution().day)];
+                terminal.draw(|frame| self.draw(frame))?;
+                self.run_selected();
+            } else if !self.on_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn solution(&self) -> &'static DaySolution {
+        &SOLUTIONS[self.days.selected().unwrap_or(0)]
+    }
+
+    /// Updates the selection for `code`, returns `false` when asked to quit.
+    fn on_key(&mut self, code: KeyCode) -> bool {
+        let selected = self.days.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.days
+                    .select(Some((selected + SOLUTIONS.len() - 1) % SOLUTIONS.len()));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.days.select(Some((selected + 1) % SOLUTIONS.len()));
+            }
+            KeyCode::Char('1') => self.parts = vec![1],
+            KeyCode::Char('2') => self.parts = vec![2],
+            KeyCode::Char('b') => self.parts = vec![1, 2],
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE),
+            KeyCode::PageDown => {
+                let last = self.output.lines().count().saturating_sub(1) as u16;
+                self.scroll = self.scroll.saturating_add(PAGE).min(last);
+            }
+            KeyCode::Char(' ') => self.player.playing = !self.player.playing,
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.player.playing = false;
+                self.player.forward(1);
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.player.playing = false;
+                self.player.back();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.player.speed = (self.player.speed * 2).min(MAX_SPEED);
+            }
+            KeyCode::Char('-') => self.player.speed = (self.player.speed / 2).max(1),
+            KeyCode::Char('r') => self.player.restart(),
+            _ => {}
+        }
+        true
+    }
+
+    /// Runs the selected parts of the selected day on its input, recording the visualization.
+    fn run_selected(&mut self) {
+        let solution = self.solution();
+        self.scroll = 0;
+        self.output.clear();
+        self.player = Player::new(Vec::new());
+        let input = match read_data(solution.day) {
+            Ok(input) => input,
+            Err(e) => {
+                self.report = vec![format!("Day {}: {:#}", solution.day, e)];
+                return;
+            }
+        };
+        let recorder = Recorder::new();
+        let visualization = Visualization::Record(&recorder);
+        self.report = match run_day(solution, &self.parts, &input, visualization) {
+            Ok(timings) => timings
+                .first()
+                .and_then(|t| t.parse)
+                .map(|parse| format!("Day {} parsed in {:.2?}", solution.day, parse))
+                .into_iter()
+                .chain(timings.iter().map(Timing::line))
+                .collect(),
+            Err(e) => vec![e],
+        };
+        self.output = console::strip_ansi_codes(&recorder.take()).into_owned();
+        self.player = Player::new(recorder.take_animations());
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(frame.size());
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(34), Constraint::Min(0)])
+            .split(rows[0]);
+        let panes = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(columns[1]);
+
+        let items = SOLUTIONS
+            .iter()
+            .map(|s| ListItem::new(format!("Day {:>2}  {}", s.day, s.name)))
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Days"))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, columns[0], &mut self.days);
+
+        let parts = match self.parts[..] {
+            [part] => format!("Part {}", part),
+            _ => "Both parts".to_string(),
+        };
+        let title = format!("Day {}, {}", self.solution().day, parts);
+        frame.render_widget(
+            Paragraph::new(self.report.join("\n"))
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            panes[0],
+        );
+        let output = if self.player.animation().is_some() {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(panes[1]);
+            self.draw_animation(frame, halves[1]);
+            halves[0]
+        } else {
+            panes[1]
+        };
+        frame.render_widget(
+            Paragraph::new(self.output.as_str())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Visualization"),
+                )
+                .scroll((self.scroll, 0)),
+            output,
+        );
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::default().add_modifier(Modifier::DIM)),
+            rows[1],
+        );
+    }
+
+    /// Draws the current frame, following its first highlight when the grid does not fit.
+    fn draw_animation(&self, frame: &mut Frame, area: Rect) {
+        let Some(animation) = self.player.animation() else {
+            return;
+        };
+        let step = animation.step(self.player.frame);
+        let title = format!(
+            "{} {}/{} x{}{}",
+            animation.title,
+            self.player.frame + 1,
+            animation.len(),
+            self.player.speed,
+            if self.player.playing { "" } else { " paused" }
+        );
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let grid = animation.grid_at(self.player.frame);
+        // The first line is the caption.
+        let (width, height) = (
+            inner.width as usize,
+            inner.height.saturating_sub(1) as usize,
+        );
+        let (x, y) = step.highlights.first().copied().unwrap_or((0, 0));
+        let columns = grid.first().map_or(0, Vec::len);
+        let left = x
+            .saturating_sub(width / 2)
+            .min(columns.saturating_sub(width));
+        let top = y
+            .saturating_sub(height / 2)
+            .min(grid.len().saturating_sub(height));
+        let highlight = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        let lines = [Line::from(step.caption.as_str())]
+            .into_iter()
+            .chain(
+                grid.iter()
+                    .enumerate()
+                    .skip(top)
+                    .take(height)
+                    .map(|(y, row)| {
+                        let cells = row.iter().enumerate().skip(left).take(width);
+                        Line::from(
+                            cells
+                                .map(|(x, c)| {
+                                    if step.highlights.contains(&(x, y)) {
+                                        Span::styled(c.to_string(), highlight)
+                                    } else {
+                                        Span::raw(c.to_string())
+                                    }
+                                })
+                                .collect::<Vec<_>>(),
+                        )
+                    }),
+            )
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+/// Plays back the animations of the last run, one after the other.
+struct Player {
+    animations: Vec<Animation>,
+    /// The index of the animation shown.
+    current: usize,
+    frame: usize,
+    playing: bool,
+    /// How many frames each tick moves forward.
+    speed: usize,
+}
+
+impl Player {
+    fn new(mut animations: Vec<Animation>) -> Self {
+        animations.retain(|animation| !animation.is_empty());
+        Self {
+            playing: !animations.is_empty(),
+            animations,
+            current: 0,
+            frame: 0,
+            speed: 1,
+        }
+    }
+
+    fn animation(&self) -> Option<&Animation> {
+        self.animations.get(self.current)
+    }
+
+    /// Moves `frames` forward, going on with the next animation at the end of one, and
+    /// pauses on the last frame of the last one.
+    fn forward(&mut self, mut frames: usize) {
+        while let Some(animation) = self.animation() {
+            let left = animation.len() - 1 - self.frame;
+            if frames <= left {
+                self.frame += frames;
+                return;
+            }
+            if self.current + 1 == self.animations.len() {
+                self.frame = animation.len() - 1;
+                self.playing = false;
+                return;
+            }
+            frames -= left + 1;
+            self.current += 1;
+            self.frame = 0;
+        }
+    }
+
+    fn back(&mut self) {
+        if self.frame > 0 {
+            self.frame -= 1;
+        } else if self.current > 0 {
+            self.current -= 1;
+            self.frame = self.animations[self.current].len() - 1;
+        }
+    }
+
+    fn restart(&mut self) {
+        self.current = 0;
+        self.frame = 0;
+        self.playing = !self.animations.is_empty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2023::Step;
+
+    #[test]
+    fn test_keys() {
+        let mut app = App::new();
+        assert!(app.on_key(KeyCode::Up));
+        assert_eq!(app.solution().day, 25);
+        assert!(app.on_key(KeyCode::Down));
+        assert!(app.on_key(KeyCode::Char('j')));
+        assert_eq!(app.solution().day, 2);
+
+        assert!(app.on_key(KeyCode::Char('2')));
+        assert_eq!(app.parts, vec![2]);
+        assert!(app.on_key(KeyCode::Char('b')));
+        assert_eq!(app.parts, vec![1, 2]);
+
+        assert!(!app.on_key(KeyCode::Char('q')));
+    }
+
+    fn animation(frames: usize) -> Animation {
+        let mut animation = Animation::new("Walk", ["..."]);
+        for x in 0..frames {
+            animation.push(Step {
+                changes: vec![(x, 0, '#')],
+                ..Step::default()
+            });
+        }
+        animation
+    }
+
+    #[test]
+    fn test_player() {
+        let mut player = Player::new(vec![animation(3), animation(0), animation(2)]);
+        assert_eq!(player.animations.len(), 2);
+        assert!(player.playing);
+        player.forward(2);
+        assert_eq!((player.current, player.frame), (0, 2));
+        player.forward(1);
+        assert_eq!((player.current, player.frame), (1, 0));
+        player.back();
+        assert_eq!((player.current, player.frame), (0, 2));
+        player.forward(10);
+        assert_eq!((player.current, player.frame), (1, 1));
+        assert!(!player.playing);
+        player.restart();
+        assert_eq!((player.current, player.frame, player.playing), (0, 0, true));
+    }
+
+    #[test]
+    fn test_player_keys() {
+        let mut app = App::new();
+        app.player = Player::new(vec![animation(3)]);
+        app.on_key(KeyCode::Right);
+        assert_eq!((app.player.frame, app.player.playing), (1, false));
+        app.on_key(KeyCode::Char(' '));
+        assert!(app.player.playing);
+        app.on_key(KeyCode::Char('+'));
+        app.on_key(KeyCode::Char('+'));
+        assert_eq!(app.player.speed, 4);
+        app.on_key(KeyCode::Char('-'));
+        assert_eq!(app.player.speed, 2);
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut app = App::new();
+        app.output = "line\n".repeat(30);
+        app.on_key(KeyCode::PageDown);
+        assert_eq!(app.scroll, 20);
+        app.on_key(KeyCode::PageDown);
+        assert_eq!(app.scroll, 29);
+        app.on_key(KeyCode::PageUp);
+        app.on_key(KeyCode::PageUp);
+        assert_eq!(app.scroll, 0);
+    }
+}